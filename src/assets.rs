@@ -18,10 +18,13 @@ struct Assets;
 static LIBRARY: OnceCell<Library> = OnceCell::new();
 
 #[cfg(target_os = "windows")]
-const LIBRARY_NAME: &'static str = "FTD3XX.dll";
+const LIBRARY_NAME: &str = "FTD3XX.dll";
 
 #[cfg(target_os = "linux")]
-const LIBRARY_NAME: &'static str = "libftd3xx.so";
+const LIBRARY_NAME: &str = "libftd3xx.so";
+
+#[cfg(target_os = "macos")]
+const LIBRARY_NAME: &str = "libftd3xx.dylib";
 
 /// Load the dynamic library at the given path.
 ///
@@ -49,6 +52,8 @@ pub fn load_dylib(path: impl AsRef<Path>) -> Result<()> {
 ///
 /// # Errors
 /// - [`D3xxError::LibraryAlreadyLoaded`] if the library has already been loaded.
+/// - [`D3xxError::NotSupported`] if no library is bundled for this platform.
+///   This is the case on macOS, where [`load_dylib`] must be used instead.
 /// - [`D3xxError::LibraryLoadFailed`] if the library could not be loaded.
 pub fn load_bundled_dylib() -> Result<()> {
     static TEMP_DIR: OnceCell<TempDir> = OnceCell::new();
@@ -57,13 +62,12 @@ pub fn load_bundled_dylib() -> Result<()> {
         return Err(D3xxError::LibraryAlreadyLoaded);
     }
 
+    let asset = Assets::get(LIBRARY_NAME).ok_or(D3xxError::NotSupported)?;
     let dylib_path = TEMP_DIR
         .get_or_try_init(tempfile::tempdir)?
         .path()
         .join(LIBRARY_NAME);
-    let asset = Assets::get(LIBRARY_NAME).expect("library asset not found");
-    let _ = File::create(&dylib_path)?
-        .write_all(asset.data.as_ref())?;
+    File::create(&dylib_path)?.write_all(asset.data.as_ref())?;
     load_dylib(dylib_path)
 }
 
@@ -0,0 +1,261 @@
+//! Asynchronous access to a [`Device`], enabled by the `tokio` feature.
+//!
+//! D3XX transfers are blocking, so every operation is run on tokio's blocking
+//! thread pool to keep the async runtime's worker threads free.
+
+use std::{
+    future::Future,
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    sync::Mutex,
+};
+
+use crate::{D3xxError, Device, Pipe, Result};
+
+type BoxedRead = Pin<Box<dyn Future<Output = Result<(Vec<u8>, Result<usize>)>> + Send>>;
+type BoxedWrite = Pin<Box<dyn Future<Output = Result<Result<usize>>> + Send>>;
+
+/// A [`Device`] which can be shared between async tasks.
+///
+/// Each clone refers to the same underlying device, and every operation
+/// locks the device for its full duration.
+///
+/// ```no_run
+/// use ft60x_rs::{AsyncDevice, Device, Pipe};
+///
+/// # async fn example() -> Result<(), ft60x_rs::D3xxError> {
+/// let device = AsyncDevice::new(Device::open_by_index(0)?);
+/// device.write(Pipe::Out0, &[0; 16]).await?;
+/// let mut buf = [0; 16];
+/// device.read(Pipe::In0, &mut buf).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsyncDevice {
+    inner: Arc<Mutex<Device>>,
+}
+
+impl AsyncDevice {
+    /// Wrap the given device for asynchronous access.
+    pub fn new(device: Device) -> AsyncDevice {
+        Self {
+            inner: Arc::new(Mutex::new(device)),
+        }
+    }
+
+    /// Writes data to the specified pipe. See [`Device::write`].
+    pub async fn write(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
+        let data = buf.to_vec();
+        run_blocking(Arc::clone(&self.inner), move |device| {
+            device.write(pipe, &data)
+        })
+        .await?
+    }
+
+    /// Reads data from the specified pipe. See [`Device::read`].
+    pub async fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        let (data, result) = read_owned(Arc::clone(&self.inner), pipe, buf.len()).await?;
+        let n = result?;
+        buf[..n].copy_from_slice(&data[..n]);
+        Ok(n)
+    }
+
+    /// Create an [`AsyncRead`] adapter for the given IN pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an IN pipe.
+    pub fn pipe_reader(&self, pipe: Pipe) -> Result<AsyncPipeReader> {
+        if !pipe.is_read_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(AsyncPipeReader {
+            device: Arc::clone(&self.inner),
+            pipe,
+            pending: None,
+            leftover: Vec::new(),
+        })
+    }
+
+    /// Create an [`AsyncWrite`] adapter for the given OUT pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an OUT pipe.
+    pub fn pipe_writer(&self, pipe: Pipe) -> Result<AsyncPipeWriter> {
+        if !pipe.is_write_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(AsyncPipeWriter {
+            device: Arc::clone(&self.inner),
+            pipe,
+            pending: None,
+        })
+    }
+}
+
+impl From<Device> for AsyncDevice {
+    fn from(device: Device) -> Self {
+        Self::new(device)
+    }
+}
+
+/// Reads from an IN pipe through the [`AsyncRead`] trait.
+///
+/// Created using [`AsyncDevice::pipe_reader`].
+pub struct AsyncPipeReader {
+    device: Arc<Mutex<Device>>,
+    pipe: Pipe,
+    pending: Option<BoxedRead>,
+    /// Data read which did not fit into the caller's buffer.
+    leftover: Vec<u8>,
+}
+
+impl AsyncPipeReader {
+    /// The pipe being read from.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+}
+
+impl AsyncRead for AsyncPipeReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+        let this = &mut *self;
+        if this.leftover.is_empty() {
+            let pending = this.pending.get_or_insert_with(|| {
+                Box::pin(read_owned(
+                    Arc::clone(&this.device),
+                    this.pipe,
+                    buf.remaining(),
+                ))
+            });
+            let result = ready!(pending.as_mut().poll(cx));
+            this.pending = None;
+            let (mut data, result) = result?;
+            data.truncate(result?);
+            this.leftover = data;
+        }
+        let n = this.leftover.len().min(buf.remaining());
+        buf.put_slice(&this.leftover[..n]);
+        this.leftover.drain(..n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl std::fmt::Debug for AsyncPipeReader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncPipeReader")
+            .field("pipe", &self.pipe)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writes to an OUT pipe through the [`AsyncWrite`] trait.
+///
+/// Created using [`AsyncDevice::pipe_writer`].
+pub struct AsyncPipeWriter {
+    device: Arc<Mutex<Device>>,
+    pipe: Pipe,
+    pending: Option<BoxedWrite>,
+}
+
+impl AsyncPipeWriter {
+    /// The pipe being written to.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// Wait for the write in progress, if any.
+    fn poll_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Option<usize>>> {
+        let Some(pending) = self.pending.as_mut() else {
+            return Poll::Ready(Ok(None));
+        };
+        let result = ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+        Poll::Ready(Ok(Some(result??)))
+    }
+}
+
+impl AsyncWrite for AsyncPipeWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+        if this.pending.is_none() {
+            let pipe = this.pipe;
+            let data = buf.to_vec();
+            this.pending = Some(Box::pin(run_blocking(
+                Arc::clone(&this.device),
+                move |device| device.write(pipe, &data),
+            )));
+        }
+        // A pending write was started with the same data by an earlier call,
+        // as required by the `AsyncWrite` contract.
+        Poll::Ready(Ok(ready!(this.poll_pending(cx))?.unwrap_or(0)))
+    }
+
+    /// Writes are not buffered, so this only waits for a write in progress.
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_pending(cx))?;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl std::fmt::Debug for AsyncPipeWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncPipeWriter")
+            .field("pipe", &self.pipe)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Read up to `len` bytes into a new buffer on the blocking thread pool.
+///
+/// The buffer is returned alongside the result of the read.
+async fn read_owned(
+    device: Arc<Mutex<Device>>,
+    pipe: Pipe,
+    len: usize,
+) -> Result<(Vec<u8>, Result<usize>)> {
+    run_blocking(device, move |device| {
+        let mut data = vec![0; len];
+        let result = device.read(pipe, &mut data);
+        (data, result)
+    })
+    .await
+}
+
+/// Lock the device and run `f` on the blocking thread pool.
+///
+/// # Errors
+/// Returns [`D3xxError::OperationAborted`] if the runtime is shutting down.
+async fn run_blocking<T, F>(device: Arc<Mutex<Device>>, f: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Device) -> T + Send + 'static,
+{
+    let device = device.lock_owned().await;
+    match tokio::task::spawn_blocking(move || f(&device)).await {
+        Ok(value) => Ok(value),
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(_) => Err(D3xxError::OperationAborted),
+    }
+}
@@ -0,0 +1,107 @@
+//! Buffers allocated with a specific alignment.
+
+use std::{
+    alloc::{self, Layout},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use crate::error::AllocationError;
+
+/// A zero-initialized heap buffer whose start is aligned to a given boundary.
+///
+/// Page-aligned buffers can be mapped for DMA by the D3XX driver directly,
+/// whereas a misaligned buffer may be copied through an intermediate buffer
+/// first. The difference is most noticeable for large transfers at
+/// SuperSpeed, and depends on the host controller and driver version, so
+/// measure it on the target system before relying on it.
+///
+/// The buffer dereferences to `[u8]`, so it can be passed straight to
+/// [`Device::read`](crate::Device::read) and [`Device::write`](crate::Device::write):
+///
+/// ```no_run
+/// use ft60x_rs::{AlignedBuffer, Device, Pipe};
+///
+/// let device = Device::open_by_index(0)?;
+/// let mut buf = AlignedBuffer::dma_safe(1 << 20).expect("allocation failed");
+/// device.read(Pipe::In0, &mut buf)?;
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug)]
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl AlignedBuffer {
+    /// The alignment used by [`AlignedBuffer::dma_safe`], one memory page.
+    pub const PAGE_SIZE: usize = 4096;
+
+    /// Allocate a buffer of `size` bytes aligned to `alignment` bytes.
+    ///
+    /// # Errors
+    /// - [`AllocationError::InvalidLayout`] if `size` is zero or too large, or
+    ///   `alignment` is not a power of two.
+    /// - [`AllocationError::OutOfMemory`] if the allocation fails.
+    pub fn new(size: usize, alignment: usize) -> Result<AlignedBuffer, AllocationError> {
+        let layout =
+            Layout::from_size_align(size, alignment).or(Err(AllocationError::InvalidLayout))?;
+        if size == 0 {
+            return Err(AllocationError::InvalidLayout);
+        }
+        // SAFETY: the layout has a non-zero size.
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).ok_or(AllocationError::OutOfMemory)?;
+        Ok(Self { ptr, layout })
+    }
+
+    /// Allocate a buffer of `size` bytes aligned to a page boundary.
+    ///
+    /// # Errors
+    /// See [`AlignedBuffer::new`].
+    pub fn dma_safe(size: usize) -> Result<AlignedBuffer, AllocationError> {
+        Self::new(size, Self::PAGE_SIZE)
+    }
+
+    /// The alignment of the buffer in bytes.
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// The contents of the buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: the allocation is initialized and `layout.size()` bytes long.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.layout.size()) }
+    }
+
+    /// The contents of the buffer.
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: as above, and the buffer is borrowed mutably.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        // SAFETY: the pointer was allocated with this layout.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+// The buffer owns its allocation like a `Box<[u8]>` does.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
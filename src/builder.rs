@@ -0,0 +1,114 @@
+//! Opening and configuring a device in a single step.
+
+use std::time::Duration;
+
+use crate::{D3xxError, Device, Pipe, Result};
+
+/// Builder for opening a [`Device`] and applying its configuration before
+/// any I/O takes place.
+///
+/// Exactly one of [`with_serial_number`](DeviceBuilder::with_serial_number),
+/// [`with_description`](DeviceBuilder::with_description),
+/// [`with_index`](DeviceBuilder::with_index) or
+/// [`with_location`](DeviceBuilder::with_location) must be given to select
+/// the device to open.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use ft60x_rs::{DeviceBuilder, Pipe};
+///
+/// let device = DeviceBuilder::new()
+///     .with_serial_number("000000000001")
+///     .with_pipe_timeout(Pipe::In0, Duration::from_millis(100))
+///     .with_stream_size(Pipe::In0, Some(4096))
+///     .build()?;
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeviceBuilder {
+    serial_number: Option<String>,
+    description: Option<String>,
+    index: Option<usize>,
+    location: Option<u32>,
+    timeouts: Vec<(Pipe, Duration)>,
+    stream_sizes: Vec<(Pipe, Option<u32>)>,
+}
+
+impl DeviceBuilder {
+    /// Create a new builder with no options set.
+    pub fn new() -> DeviceBuilder {
+        Self::default()
+    }
+
+    /// Open the device with the given serial number.
+    pub fn with_serial_number(mut self, serial_number: &str) -> Self {
+        self.serial_number = Some(serial_number.to_owned());
+        self
+    }
+
+    /// Open the device with the given product description.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_owned());
+        self
+    }
+
+    /// Open the device at the given index in the D3XX device list.
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Open the device with the given location identifier.
+    pub fn with_location(mut self, location_id: u32) -> Self {
+        self.location = Some(location_id);
+        self
+    }
+
+    /// Set the timeout for the given pipe. See [`Device::set_timeout`].
+    pub fn with_pipe_timeout(mut self, pipe: Pipe, timeout: Duration) -> Self {
+        self.timeouts.push((pipe, timeout));
+        self
+    }
+
+    /// Set the stream size for the given pipe. See [`Device::set_stream_size`].
+    pub fn with_stream_size(mut self, pipe: Pipe, stream_size: Option<u32>) -> Self {
+        self.stream_sizes.push((pipe, stream_size));
+        self
+    }
+
+    /// Open the device and apply the configuration.
+    ///
+    /// # Errors
+    /// - [`D3xxError::InvalidOptions`] if not exactly one way of selecting the
+    ///   device was given.
+    /// - Any error from opening or configuring the device. The device is closed
+    ///   again if configuration fails.
+    pub fn build(self) -> Result<Device> {
+        let device = match (
+            self.serial_number,
+            self.description,
+            self.index,
+            self.location,
+        ) {
+            (Some(serial_number), None, None, None) => {
+                Device::open_with_serial_number(&serial_number)?
+            }
+            (None, Some(description), None, None) => Device::open_by_description(&description)?,
+            (None, None, Some(index), None) => Device::open_by_index(index)?,
+            (None, None, None, Some(location)) => Device::open_by_location(location)?,
+            (None, None, None, None) => Err(D3xxError::InvalidOptions(
+                "no serial number, description, index, or location given",
+            ))?,
+            _ => Err(D3xxError::InvalidOptions(
+                "only one of serial number, description, index, or location may be given",
+            ))?,
+        };
+        for (pipe, timeout) in self.timeouts {
+            device.set_timeout(pipe, timeout)?;
+        }
+        for (pipe, stream_size) in self.stream_sizes {
+            device.set_stream_size(pipe, stream_size)?;
+        }
+        Ok(device)
+    }
+}
@@ -0,0 +1,710 @@
+//! FT60x chip configuration.
+
+use std::{
+    fmt::Debug,
+    ops::{BitOr, BitOrAssign},
+};
+
+use crate::{
+    ffi::{constants, types},
+    D3xxError, Device, Result,
+};
+
+/// The chip configuration of an FT60x device.
+///
+/// The configuration is stored in the device's EEPROM and is read with
+/// [`Device::chip_config`](crate::Device::chip_config) and written with
+/// [`Device::set_chip_config`](crate::Device::set_chip_config). The device
+/// re-enumerates after a new configuration is written.
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct ChipConfiguration {
+    pub(crate) inner: types::FT_60XCONFIGURATION,
+}
+
+impl ChipConfiguration {
+    /// The size of the configuration as stored on the chip, in bytes.
+    pub const SIZE: usize = 152;
+
+    /// Decode a configuration from its on-chip layout, e.g. from an EEPROM dump.
+    ///
+    /// Multi-byte fields are little-endian. No validation is done; see
+    /// [`ChipConfiguration::validate`].
+    pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> ChipConfiguration {
+        let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        let mut string_descriptors = [0; 128];
+        string_descriptors.copy_from_slice(&bytes[4..132]);
+        ChipConfiguration {
+            inner: types::FT_60XCONFIGURATION {
+                VendorID: u16_at(0),
+                ProductID: u16_at(2),
+                StringDescriptors: string_descriptors,
+                Reserved: bytes[132],
+                PowerAttributes: bytes[133],
+                PowerConsumption: u16_at(134),
+                Reserved2: bytes[136],
+                FIFOClock: bytes[137],
+                FIFOMode: bytes[138],
+                ChannelConfig: bytes[139],
+                OptionalFeatureSupport: u16_at(140),
+                BatteryChargingGPIOConfig: bytes[142],
+                FlashEEPROMDetection: bytes[143],
+                MSIO_Control: u32_at(144),
+                GPIO_Control: u32_at(148),
+            },
+        }
+    }
+
+    /// Encode the configuration in its on-chip layout. The inverse of
+    /// [`ChipConfiguration::from_bytes`].
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let c = &self.inner;
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..2].copy_from_slice(&c.VendorID.to_le_bytes());
+        bytes[2..4].copy_from_slice(&c.ProductID.to_le_bytes());
+        bytes[4..132].copy_from_slice(&c.StringDescriptors);
+        bytes[132] = c.Reserved;
+        bytes[133] = c.PowerAttributes;
+        bytes[134..136].copy_from_slice(&c.PowerConsumption.to_le_bytes());
+        bytes[136] = c.Reserved2;
+        bytes[137] = c.FIFOClock;
+        bytes[138] = c.FIFOMode;
+        bytes[139] = c.ChannelConfig;
+        bytes[140..142].copy_from_slice(&c.OptionalFeatureSupport.to_le_bytes());
+        bytes[142] = c.BatteryChargingGPIOConfig;
+        bytes[143] = c.FlashEEPROMDetection;
+        bytes[144..148].copy_from_slice(&c.MSIO_Control.to_le_bytes());
+        bytes[148..152].copy_from_slice(&c.GPIO_Control.to_le_bytes());
+        bytes
+    }
+
+    /// Create a [`ChipConfigurationBuilder`] starting from the device's current configuration.
+    pub fn builder(device: &Device) -> Result<ChipConfigurationBuilder<'_>> {
+        Ok(ChipConfigurationBuilder {
+            device,
+            config: device.chip_config()?,
+        })
+    }
+
+    /// Check that the configuration is consistent before writing it to a device.
+    ///
+    /// The FIFO clock, FIFO mode and channel configuration must be valid,
+    /// FT245 mode must use a single channel, and the string descriptors must
+    /// decode.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidOptions`] describing the first check which fails.
+    pub fn validate(&self) -> Result<()> {
+        self.fifo_clock()
+            .or(Err(D3xxError::InvalidOptions("invalid FIFO clock")))?;
+        let mode = self
+            .fifo_mode()
+            .or(Err(D3xxError::InvalidOptions("invalid FIFO mode")))?;
+        let channels = self.channel_config().or(Err(D3xxError::InvalidOptions(
+            "invalid channel configuration",
+        )))?;
+        if mode == FifoMode::Mode245 && channels.num_channels() != 1 {
+            Err(D3xxError::InvalidOptions(
+                "245 mode requires a single channel",
+            ))?;
+        }
+        for index in 0..3 {
+            self.string_descriptor(index)
+                .or(Err(D3xxError::InvalidOptions("invalid string descriptors")))?;
+        }
+        Ok(())
+    }
+
+    /// The USB vendor ID.
+    pub fn vendor_id(&self) -> u16 {
+        self.inner.VendorID
+    }
+
+    /// Set the USB vendor ID.
+    pub fn set_vendor_id(&mut self, vendor_id: u16) {
+        self.inner.VendorID = vendor_id;
+    }
+
+    /// The USB product ID.
+    pub fn product_id(&self) -> u16 {
+        self.inner.ProductID
+    }
+
+    /// Set the USB product ID.
+    pub fn set_product_id(&mut self, product_id: u16) {
+        self.inner.ProductID = product_id;
+    }
+
+    /// The manufacturer string descriptor.
+    pub fn manufacturer(&self) -> Result<String> {
+        self.string_descriptor(0)
+    }
+
+    /// The product description string descriptor.
+    pub fn product_description(&self) -> Result<String> {
+        self.string_descriptor(1)
+    }
+
+    /// The serial number string descriptor.
+    pub fn serial_number(&self) -> Result<String> {
+        self.string_descriptor(2)
+    }
+
+    /// Decode one of the USB string descriptors stored back-to-back in the configuration.
+    fn string_descriptor(&self, index: usize) -> Result<String> {
+        let data = &self.inner.StringDescriptors;
+        let mut offset = 0;
+        for _ in 0..index {
+            offset += data[offset] as usize;
+            if offset >= data.len() {
+                return Err(D3xxError::malformed_descriptor());
+            }
+        }
+        let len = data[offset] as usize;
+        let descriptor = data
+            .get(offset + 2..offset + len)
+            .ok_or_else(D3xxError::malformed_descriptor)?;
+        let utf16: Vec<u16> = descriptor
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        String::from_utf16(&utf16).or(Err(D3xxError::malformed_descriptor()))
+    }
+
+    /// The `bmAttributes` field of the USB configuration descriptor.
+    pub fn power_attributes(&self) -> u8 {
+        self.inner.PowerAttributes
+    }
+
+    /// Set the `bmAttributes` field of the USB configuration descriptor.
+    pub fn set_power_attributes(&mut self, attributes: u8) {
+        self.inner.PowerAttributes = attributes;
+    }
+
+    /// The maximum power consumption from the bus in milliamps.
+    pub fn power_consumption(&self) -> u16 {
+        self.inner.PowerConsumption
+    }
+
+    /// Set the maximum power consumption from the bus in milliamps.
+    pub fn set_power_consumption(&mut self, milliamps: u16) {
+        self.inner.PowerConsumption = milliamps;
+    }
+
+    /// The FIFO interface clock.
+    pub fn fifo_clock(&self) -> Result<FifoClock> {
+        FifoClock::try_from(self.inner.FIFOClock)
+    }
+
+    /// Set the FIFO interface clock.
+    pub fn set_fifo_clock(&mut self, clock: FifoClock) {
+        self.inner.FIFOClock = clock as u8;
+    }
+
+    /// The FIFO interface mode.
+    pub fn fifo_mode(&self) -> Result<FifoMode> {
+        FifoMode::try_from(self.inner.FIFOMode)
+    }
+
+    /// Set the FIFO interface mode.
+    pub fn set_fifo_mode(&mut self, mode: FifoMode) {
+        self.inner.FIFOMode = mode as u8;
+    }
+
+    /// The channel configuration.
+    pub fn channel_config(&self) -> Result<ChannelConfig> {
+        ChannelConfig::try_from(self.inner.ChannelConfig)
+    }
+
+    /// Set the channel configuration.
+    pub fn set_channel_config(&mut self, config: ChannelConfig) {
+        self.inner.ChannelConfig = config as u8;
+    }
+
+    /// The optional features which are enabled.
+    pub fn optional_features(&self) -> OptionalFeatures {
+        OptionalFeatures(self.inner.OptionalFeatureSupport)
+    }
+
+    /// Set the optional features which are enabled.
+    pub fn set_optional_features(&mut self, features: OptionalFeatures) {
+        self.inner.OptionalFeatureSupport = features.bits();
+    }
+
+    /// The raw battery charging GPIO configuration.
+    pub fn battery_charging_gpio_config(&self) -> u8 {
+        self.inner.BatteryChargingGPIOConfig
+    }
+
+    /// Set the raw battery charging GPIO configuration.
+    pub fn set_battery_charging_gpio_config(&mut self, config: u8) {
+        self.inner.BatteryChargingGPIOConfig = config;
+    }
+
+    /// The flash/EEPROM detection status. This field is read-only.
+    pub fn flash_eeprom_detection(&self) -> FlashEepromDetection {
+        FlashEepromDetection(self.inner.FlashEEPROMDetection)
+    }
+
+    /// The raw MSIO control register.
+    pub fn msio_control(&self) -> u32 {
+        self.inner.MSIO_Control
+    }
+
+    /// Set the raw MSIO control register.
+    pub fn set_msio_control(&mut self, control: u32) {
+        self.inner.MSIO_Control = control;
+    }
+
+    /// The raw GPIO control register.
+    pub fn gpio_control(&self) -> u32 {
+        self.inner.GPIO_Control
+    }
+
+    /// Set the raw GPIO control register.
+    pub fn set_gpio_control(&mut self, control: u32) {
+        self.inner.GPIO_Control = control;
+    }
+}
+
+impl Debug for ChipConfiguration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// The clock driven on the FIFO interface.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FifoClock {
+    /// 100 MHz.
+    Clock100MHz = constants::CONFIGURATION_FIFO_CLK_100 as isize,
+    /// 66 MHz.
+    Clock66MHz = constants::CONFIGURATION_FIFO_CLK_66 as isize,
+    /// 50 MHz.
+    Clock50MHz = constants::CONFIGURATION_FIFO_CLK_50 as isize,
+    /// 40 MHz.
+    Clock40MHz = constants::CONFIGURATION_FIFO_CLK_40 as isize,
+}
+
+impl TryFrom<u8> for FifoClock {
+    type Error = D3xxError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            constants::CONFIGURATION_FIFO_CLK_100 => Ok(FifoClock::Clock100MHz),
+            constants::CONFIGURATION_FIFO_CLK_66 => Ok(FifoClock::Clock66MHz),
+            constants::CONFIGURATION_FIFO_CLK_50 => Ok(FifoClock::Clock50MHz),
+            constants::CONFIGURATION_FIFO_CLK_40 => Ok(FifoClock::Clock40MHz),
+            _ => Err(D3xxError::InvalidParameter),
+        }
+    }
+}
+
+/// The protocol used on the FIFO interface.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FifoMode {
+    /// FT245 synchronous FIFO mode, supporting a single channel.
+    Mode245 = constants::CONFIGURATION_FIFO_MODE_245 as isize,
+    /// FT600 multi-channel FIFO mode.
+    Mode600 = constants::CONFIGURATION_FIFO_MODE_600 as isize,
+}
+
+impl TryFrom<u8> for FifoMode {
+    type Error = D3xxError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            constants::CONFIGURATION_FIFO_MODE_245 => Ok(FifoMode::Mode245),
+            constants::CONFIGURATION_FIFO_MODE_600 => Ok(FifoMode::Mode600),
+            _ => Err(D3xxError::InvalidParameter),
+        }
+    }
+}
+
+/// The number of channels (pairs of IN and OUT pipes) and their directions.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChannelConfig {
+    /// Four channels, each with an IN and OUT pipe.
+    Quad = constants::CONFIGURATION_CHANNEL_CONFIG_4 as isize,
+    /// Two channels, each with an IN and OUT pipe.
+    Double = constants::CONFIGURATION_CHANNEL_CONFIG_2 as isize,
+    /// One channel with an IN and OUT pipe.
+    Single = constants::CONFIGURATION_CHANNEL_CONFIG_1 as isize,
+    /// One channel with only an OUT pipe.
+    SingleOutPipe = constants::CONFIGURATION_CHANNEL_CONFIG_1_OUTPIPE as isize,
+    /// One channel with only an IN pipe.
+    SingleInPipe = constants::CONFIGURATION_CHANNEL_CONFIG_1_INPIPE as isize,
+}
+
+impl ChannelConfig {
+    /// The number of channels.
+    pub fn num_channels(&self) -> usize {
+        match self {
+            ChannelConfig::Quad => 4,
+            ChannelConfig::Double => 2,
+            ChannelConfig::Single | ChannelConfig::SingleOutPipe | ChannelConfig::SingleInPipe => 1,
+        }
+    }
+}
+
+impl TryFrom<u8> for ChannelConfig {
+    type Error = D3xxError;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            constants::CONFIGURATION_CHANNEL_CONFIG_4 => Ok(ChannelConfig::Quad),
+            constants::CONFIGURATION_CHANNEL_CONFIG_2 => Ok(ChannelConfig::Double),
+            constants::CONFIGURATION_CHANNEL_CONFIG_1 => Ok(ChannelConfig::Single),
+            constants::CONFIGURATION_CHANNEL_CONFIG_1_OUTPIPE => Ok(ChannelConfig::SingleOutPipe),
+            constants::CONFIGURATION_CHANNEL_CONFIG_1_INPIPE => Ok(ChannelConfig::SingleInPipe),
+            _ => Err(D3xxError::InvalidParameter),
+        }
+    }
+}
+
+/// Set of optional chip features.
+///
+/// Features can be combined using `|`:
+///
+/// ```
+/// use ft60x_rs::OptionalFeatures;
+///
+/// let features = OptionalFeatures::BATTERY_CHARGING | OptionalFeatures::notification_message(0);
+/// assert!(features.contains(OptionalFeatures::BATTERY_CHARGING));
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct OptionalFeatures(u16);
+
+impl OptionalFeatures {
+    /// No optional features.
+    pub const NONE: OptionalFeatures = OptionalFeatures(0);
+    /// Battery charging detection.
+    pub const BATTERY_CHARGING: OptionalFeatures =
+        OptionalFeatures(constants::CONFIGURATION_OPTIONAL_FEATURE_ENABLEBATTERYCHARGING);
+    /// Do not cancel the session when an underrun occurs.
+    pub const DISABLE_CANCEL_SESSION_UNDERRUN: OptionalFeatures =
+        OptionalFeatures(constants::CONFIGURATION_OPTIONAL_FEATURE_DISABLECANCELSESSIONUNDERRUN);
+    /// Keep the FIFO interface active while the device is suspended.
+    pub const FIFO_IN_SUSPEND: OptionalFeatures =
+        OptionalFeatures(constants::CONFIGURATION_OPTIONAL_FEATURE_SUPPORT_ENABLE_FIFO_IN_SUSPEND);
+    /// Do not power down the chip while the device is suspended.
+    pub const DISABLE_CHIP_POWERDOWN: OptionalFeatures =
+        OptionalFeatures(constants::CONFIGURATION_OPTIONAL_FEATURE_SUPPORT_DISABLE_CHIP_POWERDOWN);
+
+    /// Notification messages for the given IN channel (0-3).
+    ///
+    /// # Panics
+    /// Panics if `channel` is not in the range 0-3.
+    pub fn notification_message(channel: u8) -> OptionalFeatures {
+        assert!(channel < 4, "invalid channel: {}", channel);
+        OptionalFeatures(
+            constants::CONFIGURATION_OPTIONAL_FEATURE_ENABLENOTIFICATIONMESSAGE_INCH1 << channel,
+        )
+    }
+
+    /// Disable underrun detection for the given IN channel (0-3).
+    ///
+    /// # Panics
+    /// Panics if `channel` is not in the range 0-3.
+    pub fn disable_underrun(channel: u8) -> OptionalFeatures {
+        assert!(channel < 4, "invalid channel: {}", channel);
+        OptionalFeatures(constants::CONFIGURATION_OPTIONAL_FEATURE_DISABLEUNDERRUN_INCH1 << channel)
+    }
+
+    /// Create a set of features from raw bits.
+    pub fn from_bits(bits: u16) -> OptionalFeatures {
+        OptionalFeatures(bits)
+    }
+
+    /// The raw bits.
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Check if all of the given features are set.
+    pub fn contains(&self, other: OptionalFeatures) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Enable the given features.
+    pub fn insert(&mut self, other: OptionalFeatures) {
+        self.0 |= other.0;
+    }
+
+    /// Disable the given features.
+    pub fn remove(&mut self, other: OptionalFeatures) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for OptionalFeatures {
+    type Output = OptionalFeatures;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        OptionalFeatures(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for OptionalFeatures {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Status of the configuration memory, as detected by the chip at power-up.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct FlashEepromDetection(u8);
+
+impl FlashEepromDetection {
+    /// The raw bits.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    fn bit(&self, position: u8) -> bool {
+        self.0 & (1 << position) != 0
+    }
+
+    /// Whether the configuration memory is a ROM rather than flash.
+    pub fn is_rom(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_ROM)
+    }
+
+    /// Whether the configuration memory is absent.
+    pub fn memory_not_present(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_MEMORY_NOTEXIST)
+    }
+
+    /// Whether the custom configuration data is invalid.
+    pub fn custom_data_invalid(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_CUSTOMDATA_INVALID)
+    }
+
+    /// Whether the checksum of the custom configuration data is invalid.
+    pub fn custom_data_checksum_invalid(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_CUSTOMDATACHKSUM_INVALID)
+    }
+
+    /// Whether a custom configuration is in use rather than the default one.
+    pub fn is_custom(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_CUSTOM)
+    }
+
+    /// Whether the GPIOs select the configuration at power-up.
+    pub fn gpio_input(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_GPIO_INPUT)
+    }
+
+    /// The level of GPIO 0 at power-up.
+    pub fn gpio_0(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_GPIO_0)
+    }
+
+    /// The level of GPIO 1 at power-up.
+    pub fn gpio_1(&self) -> bool {
+        self.bit(constants::CONFIGURATION_FLASH_ROM_BIT_GPIO_1)
+    }
+}
+
+/// Builder for modifying selected fields of a device's chip configuration.
+///
+/// Created using [`ChipConfiguration::builder`], which starts from the
+/// device's current configuration. Fields which are not set keep their
+/// current values.
+///
+/// ```no_run
+/// use ft60x_rs::{ChannelConfig, ChipConfiguration, Device, FifoMode};
+///
+/// let device = Device::open_by_index(0)?;
+/// ChipConfiguration::builder(&device)?
+///     .with_fifo_mode(FifoMode::Mode245)
+///     .with_channel_config(ChannelConfig::Single)
+///     .apply()?;
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug)]
+pub struct ChipConfigurationBuilder<'a> {
+    device: &'a Device,
+    config: ChipConfiguration,
+}
+
+impl ChipConfigurationBuilder<'_> {
+    /// Set the USB vendor ID.
+    pub fn with_vendor_id(mut self, vendor_id: u16) -> Self {
+        self.config.set_vendor_id(vendor_id);
+        self
+    }
+
+    /// Set the USB product ID.
+    pub fn with_product_id(mut self, product_id: u16) -> Self {
+        self.config.set_product_id(product_id);
+        self
+    }
+
+    /// Set the `bmAttributes` field of the USB configuration descriptor.
+    pub fn with_power_attributes(mut self, attributes: u8) -> Self {
+        self.config.set_power_attributes(attributes);
+        self
+    }
+
+    /// Set the maximum power consumption from the bus in milliamps.
+    pub fn with_power_consumption(mut self, milliamps: u16) -> Self {
+        self.config.set_power_consumption(milliamps);
+        self
+    }
+
+    /// Set the FIFO interface clock.
+    pub fn with_fifo_clock(mut self, clock: FifoClock) -> Self {
+        self.config.set_fifo_clock(clock);
+        self
+    }
+
+    /// Set the FIFO interface mode.
+    pub fn with_fifo_mode(mut self, mode: FifoMode) -> Self {
+        self.config.set_fifo_mode(mode);
+        self
+    }
+
+    /// Set the channel configuration.
+    pub fn with_channel_config(mut self, config: ChannelConfig) -> Self {
+        self.config.set_channel_config(config);
+        self
+    }
+
+    /// Set the optional features which are enabled.
+    pub fn with_optional_features(mut self, features: OptionalFeatures) -> Self {
+        self.config.set_optional_features(features);
+        self
+    }
+
+    /// Set the raw battery charging GPIO configuration.
+    pub fn with_battery_charging_gpio_config(mut self, config: u8) -> Self {
+        self.config.set_battery_charging_gpio_config(config);
+        self
+    }
+
+    /// Set the raw MSIO control register.
+    pub fn with_msio_control(mut self, control: u32) -> Self {
+        self.config.set_msio_control(control);
+        self
+    }
+
+    /// Set the raw GPIO control register.
+    pub fn with_gpio_control(mut self, control: u32) -> Self {
+        self.config.set_gpio_control(control);
+        self
+    }
+
+    /// Get the modified configuration without writing it to the device.
+    pub fn build(self) -> ChipConfiguration {
+        self.config
+    }
+
+    /// Write the modified configuration to the device. See [`Device::set_chip_config`].
+    pub fn apply(self) -> Result<()> {
+        self.device.set_chip_config(&self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode strings as the back-to-back USB string descriptors stored in a configuration.
+    fn string_descriptors(strings: &[&str]) -> [u8; 128] {
+        let mut data = [0; 128];
+        let mut offset = 0;
+        for s in strings {
+            let utf16: Vec<u16> = s.encode_utf16().collect();
+            let len = 2 + 2 * utf16.len();
+            data[offset] = len as u8;
+            data[offset + 1] = 0x03;
+            for (i, c) in utf16.iter().enumerate() {
+                data[offset + 2 + 2 * i..offset + 4 + 2 * i].copy_from_slice(&c.to_le_bytes());
+            }
+            offset += len;
+        }
+        data
+    }
+
+    /// A configuration modelled on the one FTDI ships FT601 devices with.
+    fn factory_default() -> ChipConfiguration {
+        let mut config = ChipConfiguration::default();
+        config.set_vendor_id(0x0403);
+        config.set_product_id(0x601F);
+        config.inner.StringDescriptors =
+            string_descriptors(&["FTDI", "FTDI SuperSpeed-FIFO Bridge", "000000000001"]);
+        config.set_power_attributes(0xE0);
+        config.set_power_consumption(96);
+        config.set_fifo_clock(FifoClock::Clock100MHz);
+        config.set_fifo_mode(FifoMode::Mode600);
+        config.set_channel_config(ChannelConfig::Quad);
+        config
+    }
+
+    #[test]
+    fn factory_default_round_trips() {
+        let config = factory_default();
+        let bytes = config.to_bytes();
+        assert_eq!(&bytes[0..4], &[0x03, 0x04, 0x1F, 0x60]);
+        assert_eq!(&bytes[4..6], &[10, 0x03]);
+        assert_eq!(&bytes[133..140], &[0xE0, 96, 0, 0, 0, 1, 0]);
+        let decoded = ChipConfiguration::from_bytes(&bytes);
+        assert_eq!(decoded, config);
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert_eq!(decoded.manufacturer().unwrap(), "FTDI");
+        assert_eq!(
+            decoded.product_description().unwrap(),
+            "FTDI SuperSpeed-FIFO Bridge"
+        );
+        assert_eq!(decoded.serial_number().unwrap(), "000000000001");
+        decoded.validate().unwrap();
+    }
+
+    #[test]
+    fn all_zero_blob_round_trips_but_is_invalid() {
+        let bytes = [0; ChipConfiguration::SIZE];
+        let config = ChipConfiguration::from_bytes(&bytes);
+        assert_eq!(config, ChipConfiguration::default());
+        assert_eq!(config.to_bytes(), bytes);
+        // Zero selects 245 mode with four channels.
+        assert!(matches!(
+            config.validate(),
+            Err(D3xxError::InvalidOptions(
+                "245 mode requires a single channel"
+            ))
+        ));
+    }
+
+    #[test]
+    fn all_ff_blob_round_trips_but_is_invalid() {
+        let bytes = [0xFF; ChipConfiguration::SIZE];
+        let config = ChipConfiguration::from_bytes(&bytes);
+        assert_eq!(config.vendor_id(), 0xFFFF);
+        assert_eq!(config.gpio_control(), 0xFFFF_FFFF);
+        assert_eq!(config.to_bytes(), bytes);
+        assert!(matches!(
+            config.validate(),
+            Err(D3xxError::InvalidOptions("invalid FIFO clock"))
+        ));
+    }
+
+    #[test]
+    fn validate_rejects_bad_string_descriptors() {
+        let mut config = factory_default();
+        config.inner.StringDescriptors[0] = 200;
+        assert!(matches!(
+            config.validate(),
+            Err(D3xxError::InvalidOptions("invalid string descriptors"))
+        ));
+    }
+
+    #[test]
+    fn validate_accepts_245_mode_with_one_channel() {
+        let mut config = factory_default();
+        config.set_fifo_mode(FifoMode::Mode245);
+        assert!(config.validate().is_err());
+        config.set_channel_config(ChannelConfig::Single);
+        config.validate().unwrap();
+    }
+}
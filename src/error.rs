@@ -1,6 +1,6 @@
 use std::fmt::{Debug, Display};
 
-use crate::ffi::types::FT_STATUS;
+use crate::{ffi::types::FT_STATUS, Pipe};
 
 /// Error type corresponding to possible [`FT_STATUS`] errors
 #[derive(thiserror::Error, Debug)]
@@ -39,16 +39,80 @@ pub enum D3xxError {
     DeviceNotConnected,
     IncorrectDevicePath,
 
-    OtherError,
+    /// Any other error, including status codes unknown to this crate. Holds the raw status value.
+    OtherError(u32),
 
     // Errors not defined by the D3XX library
     LibraryAccessFailed(#[from] libloading::Error),
     UnpackingFailed(#[from] std::io::Error),
     LibraryAlreadyLoaded,
     LibraryNotLoaded,
+    InvalidOptions(&'static str),
+    InvalidFrame(&'static str),
 }
 
 impl D3xxError {
+    /// Whether the error may resolve itself without intervention, such that
+    /// retrying the operation could succeed.
+    pub fn is_transient(&self) -> bool {
+        self.category() == ErrorCategory::Transient
+    }
+
+    /// Whether the device is unusable, for example because it was disconnected.
+    pub fn is_fatal(&self) -> bool {
+        self.category() == ErrorCategory::Fatal
+    }
+
+    /// Broad classification of the error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            D3xxError::Timeout
+            | D3xxError::Busy
+            | D3xxError::IoPending
+            | D3xxError::IoIncomplete
+            | D3xxError::NoMoreItems
+            | D3xxError::DeviceListNotReady => ErrorCategory::Transient,
+
+            D3xxError::InvalidHandle
+            | D3xxError::DeviceNotFound
+            | D3xxError::DeviceNotConnected
+            | D3xxError::DeviceNotOpened
+            | D3xxError::IncorrectDevicePath => ErrorCategory::Fatal,
+
+            D3xxError::DeviceNotOpenedForErase
+            | D3xxError::DeviceNotOpenedForWrite
+            | D3xxError::FailedToWriteDevice
+            | D3xxError::EEPROMReadFailed
+            | D3xxError::EEPROMWriteFailed
+            | D3xxError::EEPROMEraseFailed
+            | D3xxError::EEPROMNotPresent
+            | D3xxError::EEPROMNotProgrammed => ErrorCategory::Eeprom,
+
+            D3xxError::InvalidParameter
+            | D3xxError::InvalidBaudRate
+            | D3xxError::InvalidArgs
+            | D3xxError::NotSupported
+            | D3xxError::ReservedPipe
+            | D3xxError::LibraryAccessFailed(_)
+            | D3xxError::UnpackingFailed(_)
+            | D3xxError::LibraryAlreadyLoaded
+            | D3xxError::LibraryNotLoaded
+            | D3xxError::InvalidOptions(_) => ErrorCategory::Configuration,
+
+            D3xxError::IoError
+            | D3xxError::InsufficientResources
+            | D3xxError::OperationAborted
+            | D3xxError::InvalidControlRequestDirection
+            | D3xxError::InvalidControLRequestType
+            | D3xxError::HandleEof
+            | D3xxError::NoSystemResources
+            | D3xxError::OtherError(_)
+            | D3xxError::InvalidFrame(_) => ErrorCategory::Protocol,
+        }
+    }
+
+    /// The `FT_STATUS` value of the error, or `None` for errors which do not
+    /// come from the D3XX library.
     pub fn error_code(&self) -> Option<u32> {
         match self {
             D3xxError::InvalidHandle => Some(1),
@@ -82,19 +146,43 @@ impl D3xxError {
             D3xxError::DeviceListNotReady => Some(29),
             D3xxError::DeviceNotConnected => Some(30),
             D3xxError::IncorrectDevicePath => Some(31),
-            D3xxError::OtherError => Some(32),
+            D3xxError::OtherError(code) => Some(*code),
             _ => None,
         }
     }
 }
 
-impl From<FT_STATUS> for D3xxError {
-    /// Convert from a raw status value to a `D3xxError`.
+impl D3xxError {
+    /// The `FT_STATUS` value of the error.
     ///
-    /// # Panics
-    /// Panics if the given value is not a valid status value.
-    fn from(id: FT_STATUS) -> Self {
-        match id {
+    /// Errors which do not come from the D3XX library report `FT_OTHER_ERROR` (32).
+    pub fn raw_code(&self) -> u32 {
+        const FT_OTHER_ERROR: u32 = 32;
+        self.error_code().unwrap_or(FT_OTHER_ERROR)
+    }
+
+    /// Wrap the error with the name of the operation which failed.
+    pub fn context(self, operation: &'static str) -> D3xxErrorContext {
+        D3xxErrorContext::new(self).context(operation)
+    }
+
+    /// Wrap the error with the pipe involved in the failed operation.
+    pub fn with_pipe(self, pipe: Pipe) -> D3xxErrorContext {
+        D3xxErrorContext::new(self).with_pipe(pipe)
+    }
+
+    /// The error reported when a descriptor received from the device cannot be parsed.
+    pub(crate) fn malformed_descriptor() -> Self {
+        D3xxError::InvalidFrame("malformed descriptor")
+    }
+
+    /// Convert a raw status value returned by a D3XX function to a `Result`.
+    ///
+    /// `FT_OK` (0) maps to `Ok(())`. Status values unknown to this crate map to
+    /// [`D3xxError::OtherError`] holding the raw value.
+    pub fn try_from_status(status: FT_STATUS) -> Result<(), D3xxError> {
+        let error = match status {
+            0 => return Ok(()),
             1 => D3xxError::InvalidHandle,
             2 => D3xxError::DeviceNotFound,
             3 => D3xxError::DeviceNotOpened,
@@ -126,12 +214,74 @@ impl From<FT_STATUS> for D3xxError {
             29 => D3xxError::DeviceListNotReady,
             30 => D3xxError::DeviceNotConnected,
             31 => D3xxError::IncorrectDevicePath,
-            32 => D3xxError::OtherError,
-            _ => panic!("Unknown value {}", id),
+            other => D3xxError::OtherError(other as u32),
+        };
+        Err(error)
+    }
+}
+
+impl From<FT_STATUS> for D3xxError {
+    /// Convert from a raw status value to a `D3xxError`.
+    ///
+    /// Prefer [`D3xxError::try_from_status`], which also handles `FT_OK`.
+    ///
+    /// # Panics
+    /// Panics if the given value is `FT_OK` (0), which is not an error.
+    fn from(id: FT_STATUS) -> Self {
+        match D3xxError::try_from_status(id) {
+            Ok(()) => panic!("FT_OK is not an error"),
+            Err(e) => e,
         }
     }
 }
 
+impl From<D3xxError> for std::io::Error {
+    /// Convert a `D3xxError` to an I/O error of the closest matching kind.
+    ///
+    /// The original error is the I/O error's [`source`](std::error::Error::source):
+    ///
+    /// ```
+    /// use std::error::Error;
+    /// use ft60x_rs::D3xxError;
+    ///
+    /// let e = std::io::Error::from(D3xxError::Timeout);
+    /// let source = e.source().and_then(|s| s.downcast_ref::<D3xxError>());
+    /// assert!(matches!(source, Some(D3xxError::Timeout)));
+    /// ```
+    fn from(e: D3xxError) -> Self {
+        use std::io::{Error, ErrorKind};
+
+        let kind = match e {
+            D3xxError::Timeout => ErrorKind::TimedOut,
+            D3xxError::InvalidParameter => ErrorKind::InvalidInput,
+            D3xxError::DeviceNotFound => ErrorKind::NotFound,
+            D3xxError::OperationAborted => ErrorKind::Interrupted,
+            D3xxError::Busy => ErrorKind::WouldBlock,
+            _ => ErrorKind::Other,
+        };
+        Error::new(kind, IoErrorPayload(e))
+    }
+}
+
+/// The payload of an I/O error converted from a [`D3xxError`].
+///
+/// `io::Error::source` returns the source of its payload rather than the
+/// payload itself, so the payload wraps the error to make it the source.
+#[derive(Debug)]
+struct IoErrorPayload(D3xxError);
+
+impl Display for IoErrorPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for IoErrorPayload {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
 impl Display for D3xxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name = match self {
@@ -166,12 +316,14 @@ impl Display for D3xxError {
             Self::DeviceListNotReady => "DeviceListNotReady".to_owned(),
             Self::DeviceNotConnected => "DeviceNotConnected".to_owned(),
             Self::IncorrectDevicePath => "IncorrectDevicePath".to_owned(),
-            Self::OtherError => "OtherError".to_owned(),
+            Self::OtherError(_) => "OtherError".to_owned(),
 
             Self::LibraryAccessFailed(e) => format!("LibraryAccessFailed - {}", e),
             Self::UnpackingFailed(e) => format!("UnpackingFailed - {}", e),
             Self::LibraryAlreadyLoaded => "LibraryAlreadyLoaded".to_owned(),
             Self::LibraryNotLoaded => "LibraryNotLoaded".to_owned(),
+            Self::InvalidOptions(e) => format!("InvalidOptions - {}", e),
+            Self::InvalidFrame(e) => format!("InvalidFrame - {}", e),
         };
         let code = self
             .error_code()
@@ -180,3 +332,158 @@ impl Display for D3xxError {
         write!(f, "{} (error code {})", name, code)
     }
 }
+
+/// A [`D3xxError`] along with details about the operation which failed.
+///
+/// Created using [`D3xxError::context`] or [`D3xxError::with_pipe`], for example
+/// when propagating a transfer error:
+///
+/// ```no_run
+/// use ft60x_rs::{D3xxErrorContext, Device, Pipe};
+///
+/// fn read_header(device: &Device, buf: &mut [u8]) -> Result<usize, D3xxErrorContext> {
+///     device.read(Pipe::In0, buf).map_err(|e| {
+///         e.context("read header")
+///             .with_pipe(Pipe::In0)
+///             .with_bytes_requested(buf.len())
+///     })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct D3xxErrorContext {
+    error: D3xxError,
+    operation: &'static str,
+    pipe: Option<Pipe>,
+    bytes_requested: Option<usize>,
+}
+
+impl D3xxErrorContext {
+    fn new(error: D3xxError) -> D3xxErrorContext {
+        Self {
+            error,
+            operation: "",
+            pipe: None,
+            bytes_requested: None,
+        }
+    }
+
+    /// Set the name of the operation which failed.
+    pub fn context(mut self, operation: &'static str) -> D3xxErrorContext {
+        self.operation = operation;
+        self
+    }
+
+    /// Set the pipe involved in the failed operation.
+    pub fn with_pipe(mut self, pipe: Pipe) -> D3xxErrorContext {
+        self.pipe = Some(pipe);
+        self
+    }
+
+    /// Set the number of bytes the failed operation tried to transfer.
+    pub fn with_bytes_requested(mut self, bytes_requested: usize) -> D3xxErrorContext {
+        self.bytes_requested = Some(bytes_requested);
+        self
+    }
+
+    /// The wrapped error.
+    pub fn error(&self) -> &D3xxError {
+        &self.error
+    }
+
+    /// The name of the operation which failed, or an empty string if not set.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// The pipe involved in the failed operation, if set.
+    pub fn pipe(&self) -> Option<Pipe> {
+        self.pipe
+    }
+
+    /// The number of bytes the failed operation tried to transfer, if set.
+    pub fn bytes_requested(&self) -> Option<usize> {
+        self.bytes_requested
+    }
+
+    /// Discard the context, returning the wrapped error.
+    pub fn into_inner(self) -> D3xxError {
+        self.error
+    }
+}
+
+impl Display for D3xxErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.operation.is_empty() {
+            f.write_str("operation")?;
+        } else {
+            f.write_str(self.operation)?;
+        }
+        if let Some(pipe) = self.pipe {
+            write!(f, " on pipe {}", pipe)?;
+        }
+        if let Some(bytes_requested) = self.bytes_requested {
+            write!(f, " ({} bytes requested)", bytes_requested)?;
+        }
+        write!(f, " failed: {}", self.error)
+    }
+}
+
+impl std::error::Error for D3xxErrorContext {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<D3xxErrorContext> for D3xxError {
+    fn from(e: D3xxErrorContext) -> Self {
+        e.into_inner()
+    }
+}
+
+/// Broad classification of a [`D3xxError`], see [`D3xxError::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// The error may resolve itself; retrying could succeed.
+    Transient,
+    /// The device is gone or the handle is no longer usable.
+    Fatal,
+    /// Invalid arguments, unsupported operations, or library setup problems.
+    Configuration,
+    /// Reading or writing the device's EEPROM failed.
+    Eeprom,
+    /// The transfer itself failed or was aborted.
+    Protocol,
+}
+
+/// Error returned when parsing a [`Version`](crate::Version) from a string fails.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid version string: {0:?}")]
+pub struct ParseVersionError(pub String);
+
+/// Error returned when parsing a [`Pipe`] from a string fails.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid pipe: {0:?}")]
+pub struct ParsePipeError(pub String);
+
+/// Error returned when allocating an [`AlignedBuffer`](crate::AlignedBuffer) fails.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationError {
+    /// The size is zero or too large, or the alignment is not a power of two.
+    #[error("invalid buffer size or alignment")]
+    InvalidLayout,
+    /// The allocator could not provide the memory.
+    #[error("out of memory")]
+    OutOfMemory,
+}
+
+/// Error returned when streaming pipe data into a writer fails, see
+/// [`Device::read_into_writer`](crate::Device::read_into_writer).
+#[derive(thiserror::Error, Debug)]
+pub enum ReadWriteError {
+    /// Reading from the pipe failed.
+    #[error("failed to read from pipe: {0}")]
+    Read(#[from] D3xxError),
+    /// Writing to the writer failed.
+    #[error("failed to write data: {0}")]
+    Write(#[from] std::io::Error),
+}
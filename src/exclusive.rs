@@ -0,0 +1,137 @@
+//! Exclusive access to a [`Device`] across processes.
+
+use std::ops::Deref;
+
+use crate::{D3xxError, Device, Result};
+
+/// A [`Device`] opened while holding a system-wide lock for its serial number.
+///
+/// The lock is held for the lifetime of the wrapper and released when it is
+/// dropped. It only prevents races between processes using this crate to open
+/// the device; processes opening the device by other means are not affected.
+///
+/// Created using [`Device::open_exclusive`].
+#[derive(Debug)]
+pub struct ExclusiveDevice {
+    // Declared first so the device is closed before the lock is released.
+    device: Device,
+    _lock: SystemLock,
+}
+
+impl ExclusiveDevice {
+    /// Acquire the lock for the given serial number, then open the device.
+    pub(crate) fn open(serial_number: &str) -> Result<ExclusiveDevice> {
+        let lock = SystemLock::acquire(serial_number)?;
+        Ok(Self {
+            device: Device::open_with_serial_number(serial_number)?,
+            _lock: lock,
+        })
+    }
+
+    /// Release the lock, returning the inner device.
+    pub fn into_inner(self) -> Device {
+        self.device
+    }
+}
+
+impl Deref for ExclusiveDevice {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        &self.device
+    }
+}
+
+/// Lock file held with `flock`, released when the file is closed.
+#[cfg(not(windows))]
+#[derive(Debug)]
+struct SystemLock {
+    _file: std::fs::File,
+}
+
+#[cfg(not(windows))]
+impl SystemLock {
+    fn acquire(serial_number: &str) -> Result<SystemLock> {
+        use std::os::unix::io::AsRawFd;
+
+        if serial_number.contains(['/', '\0']) {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(format!("/tmp/ft60x-{serial_number}.lock"))?;
+        // The lock file is never removed, as doing so would race with other
+        // processes acquiring it.
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let err = std::io::Error::last_os_error();
+            return match err.raw_os_error() {
+                Some(libc::EWOULDBLOCK) => Err(D3xxError::Busy),
+                _ => Err(err.into()),
+            };
+        }
+        Ok(Self { _file: file })
+    }
+}
+
+/// Named mutex, released when its handle is closed.
+#[cfg(windows)]
+#[derive(Debug)]
+struct SystemLock {
+    handle: *mut libc::c_void,
+}
+
+#[cfg(windows)]
+mod kernel32 {
+    use libc::{c_int, c_ulong, c_void, wchar_t};
+
+    pub(super) const ERROR_ALREADY_EXISTS: c_ulong = 183;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub(super) fn CreateMutexW(
+            attributes: *mut c_void,
+            initial_owner: c_int,
+            name: *const wchar_t,
+        ) -> *mut c_void;
+        pub(super) fn CloseHandle(handle: *mut c_void) -> c_int;
+        pub(super) fn GetLastError() -> c_ulong;
+    }
+}
+
+#[cfg(windows)]
+impl SystemLock {
+    fn acquire(serial_number: &str) -> Result<SystemLock> {
+        if serial_number.contains(['\\', '\0']) {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let name: Vec<u16> = format!("Global\\ft60x-{serial_number}")
+            .encode_utf16()
+            .chain(Some(0))
+            .collect();
+        let (handle, last_error) = unsafe {
+            let handle = kernel32::CreateMutexW(std::ptr::null_mut(), 0, name.as_ptr());
+            (handle, kernel32::GetLastError())
+        };
+        if handle.is_null() {
+            Err(std::io::Error::from_raw_os_error(last_error as i32))?;
+        }
+        if last_error == kernel32::ERROR_ALREADY_EXISTS {
+            unsafe { kernel32::CloseHandle(handle) };
+            Err(D3xxError::Busy)?;
+        }
+        Ok(Self { handle })
+    }
+}
+
+#[cfg(windows)]
+impl Drop for SystemLock {
+    fn drop(&mut self) {
+        unsafe { kernel32::CloseHandle(self.handle) };
+    }
+}
+
+// The mutex handle is only closed on drop, which can happen on any thread.
+#[cfg(windows)]
+unsafe impl Send for SystemLock {}
@@ -25,7 +25,7 @@ use crate::Result;
 ///
 /// # Errors
 /// Returns [`D3xxError::LibraryNotLoaded`] if the library could not be loaded.
-fn d3xx_fn<T>(name: &str) -> Result<Symbol<T>> {
+fn d3xx_fn<T>(name: &str) -> Result<Symbol<'static, T>> {
     let library = d3xx_lib()?;
     let function = unsafe { library.get::<T>(name.as_bytes())? };
     Ok(function)
@@ -42,13 +42,15 @@ pub(crate) fn ptr_mut<T, U>(x: &mut T) -> *mut U {
 /// Prototypes for these functions are defined in the `FTD3XX.h` header file.
 #[allow(non_snake_case, unused)]
 pub(crate) mod lib {
-    use libc::{c_uchar, c_ulong, c_ushort, c_void};
+    use libc::{c_int, c_uchar, c_ulong, c_ushort, c_void};
     use libloading::{Library, Symbol};
     use once_cell::sync::OnceCell;
 
     use super::d3xx_fn;
     use super::types::{
-        FT_DEVICE_DESCRIPTOR, FT_DEVICE_LIST_INFO_NODE, FT_HANDLE, FT_PIPE_INFORMATION, FT_STATUS,
+        FT_60XCONFIGURATION, FT_CONFIGURATION_DESCRIPTOR, FT_DEVICE_DESCRIPTOR,
+        FT_DEVICE_LIST_INFO_NODE, FT_HANDLE, FT_INTERFACE_DESCRIPTOR, FT_PIPE_INFORMATION,
+        FT_SETUP_PACKET, FT_STATUS,
     };
     use crate::{D3xxError, Result};
 
@@ -56,37 +58,32 @@ pub(crate) mod lib {
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```ignore
     /// wrap_d3xx!(FT_ListDevices, pArg1: *mut c_void, pArg2: *mut c_void, flags: c_ulong);
     /// ```
     ///
     /// This will generate the following function:
     ///
-    /// ```
+    /// ```ignore
     /// unsafe fn FT_ListDevices(pArg1: *mut c_void, pArg2: *mut c_void, flags: c_ulong) -> Result<()> {
     ///     type F = unsafe extern "C" fn(*mut c_void, *mut c_void, c_ulong) -> FT_STATUS;
     ///     static SYMBOL: OnceCell<Symbol<F>> = OnceCell::new();
     ///
     ///     let func = SYMBOL.get_or_try_init(|| d3xx_fn::<F>(stringify!($name)))?;
     ///     let res = unsafe { func(pArg1, pArg2, flags) };
-    ///     if res != 0 {
-    ///        return Err(D3xxError::from(res as u32).into());
-    ///     }
-    ///     Ok(())
+    ///     D3xxError::try_from_status(res)
     /// }
     /// ```
     macro_rules! wrap_d3xx {
         ($name:ident, $($arg:ident: $ty:ty),*) => {
+            #[allow(clippy::too_many_arguments)]
             pub(crate) unsafe fn $name($($arg: $ty),*) -> Result<()> {
                 type F = unsafe extern "C" fn($($ty),*) -> FT_STATUS;
                 static SYMBOL: OnceCell<Symbol<F>> = OnceCell::new();
 
                 let func = SYMBOL.get_or_try_init(|| d3xx_fn::<F>(stringify!($name)))?;
                 let res = unsafe { func($($arg),*) };
-                if res != 0 {
-                    return Err(D3xxError::from(res));
-                }
-                Ok(())
+                D3xxError::try_from_status(res)
             }
         }
     }
@@ -154,6 +151,19 @@ pub(crate) mod lib {
         pOverlapped: *mut c_void
     );
     wrap_d3xx!(FT_FlushPipe, handle: FT_HANDLE, ucPipeID: c_uchar);
+    wrap_d3xx!(
+        FT_InitializeOverlapped,
+        handle: FT_HANDLE,
+        pOverlapped: *mut c_void
+    );
+    wrap_d3xx!(
+        FT_GetOverlappedResult,
+        handle: FT_HANDLE,
+        pOverlapped: *mut c_void,
+        pulLengthTransferred: *mut c_ulong,
+        bWait: c_int
+    );
+    wrap_d3xx!(FT_ReleaseOverlapped, handle: FT_HANDLE, pOverlapped: *mut c_void);
     wrap_d3xx!(
         FT_SetPipeTimeout,
         handle: FT_HANDLE,
@@ -166,6 +176,38 @@ pub(crate) mod lib {
         ucPipeId: c_uchar,
         pTimeoutInMs: *mut c_ulong
     );
+    wrap_d3xx!(
+        FT_GetReadQueueStatus,
+        handle: FT_HANDLE,
+        ucPipeID: c_uchar,
+        lpdwAmountInQueue: *mut c_ulong
+    );
+    wrap_d3xx!(
+        FT_GetWriteQueueStatus,
+        handle: FT_HANDLE,
+        ucPipeID: c_uchar,
+        lpdwAmountInQueue: *mut c_ulong
+    );
+    wrap_d3xx!(
+        FT_SetUSBParameters,
+        handle: FT_HANDLE,
+        ulInTransferSize: c_ulong,
+        ulOutTransferSize: c_ulong
+    );
+    wrap_d3xx!(
+        FT_SetLatencyTimer,
+        handle: FT_HANDLE,
+        ucPipeID: c_uchar,
+        ucTimer: c_uchar
+    );
+    wrap_d3xx!(
+        FT_GetLatencyTimer,
+        handle: FT_HANDLE,
+        ucPipeID: c_uchar,
+        pucTimer: *mut c_uchar
+    );
+    wrap_d3xx!(FT_SetSuspendTimeout, handle: FT_HANDLE, Timeout: c_ulong);
+    wrap_d3xx!(FT_GetSuspendTimeout, handle: FT_HANDLE, Timeout: *mut c_ulong);
     wrap_d3xx!(
         FT_GetVIDPID,
         handle: FT_HANDLE,
@@ -177,6 +219,26 @@ pub(crate) mod lib {
         handle: FT_HANDLE,
         pDescriptor: *mut FT_DEVICE_DESCRIPTOR
     );
+    wrap_d3xx!(
+        FT_GetConfigurationDescriptor,
+        handle: FT_HANDLE,
+        pDescriptor: *mut FT_CONFIGURATION_DESCRIPTOR
+    );
+    wrap_d3xx!(
+        FT_GetInterfaceDescriptor,
+        handle: FT_HANDLE,
+        ucInterfaceIndex: c_uchar,
+        pDescriptor: *mut FT_INTERFACE_DESCRIPTOR
+    );
+    wrap_d3xx!(
+        FT_GetDescriptor,
+        handle: FT_HANDLE,
+        ucDescriptorType: c_uchar,
+        ucIndex: c_uchar,
+        pucBuffer: *mut c_uchar,
+        ulBufferLength: c_ulong,
+        pulLengthTransferred: *mut c_ulong
+    );
     wrap_d3xx!(
         FT_SetStreamPipe,
         handle: FT_HANDLE,
@@ -194,6 +256,7 @@ pub(crate) mod lib {
     );
     wrap_d3xx!(FT_AbortPipe, handle: FT_HANDLE, ucPipeID: c_uchar);
     wrap_d3xx!(FT_CycleDevicePort, handle: FT_HANDLE);
+    wrap_d3xx!(FT_ResetDevicePort, handle: FT_HANDLE);
     wrap_d3xx!(
         FT_GetPipeInformation,
         handle: FT_HANDLE,
@@ -202,6 +265,37 @@ pub(crate) mod lib {
         pPipeInformation: *mut FT_PIPE_INFORMATION
     );
     wrap_d3xx!(FT_GetLibraryVersion, version: *mut c_ulong);
+    wrap_d3xx!(
+        FT_GetChipConfiguration,
+        handle: FT_HANDLE,
+        pvConfiguration: *mut FT_60XCONFIGURATION
+    );
+    wrap_d3xx!(
+        FT_SetChipConfiguration,
+        handle: FT_HANDLE,
+        pvConfiguration: *mut FT_60XCONFIGURATION
+    );
+    wrap_d3xx!(
+        FT_ControlTransfer,
+        handle: FT_HANDLE,
+        tSetupPacket: FT_SETUP_PACKET,
+        pucBuffer: *mut c_uchar,
+        ulBufferLength: c_ulong,
+        pulLengthTransferred: *mut c_ulong
+    );
+    wrap_d3xx!(
+        FT_EnableGPIO,
+        handle: FT_HANDLE,
+        u32Mask: c_ulong,
+        u32Dir: c_ulong
+    );
+    wrap_d3xx!(
+        FT_WriteGPIO,
+        handle: FT_HANDLE,
+        u32Mask: c_ulong,
+        u32Data: c_ulong
+    );
+    wrap_d3xx!(FT_ReadGPIO, handle: FT_HANDLE, pu32Data: *mut c_ulong);
 }
 
 /// Types used by D3XX functions.
@@ -234,6 +328,70 @@ pub mod types {
         pub(crate) bNumConfigurations: c_uchar,
     }
 
+    #[cfg(windows)]
+    #[allow(non_snake_case, clippy::upper_case_acronyms)]
+    #[repr(C)]
+    #[derive(Debug)]
+    pub(crate) struct OVERLAPPED {
+        pub(crate) Internal: usize,
+        pub(crate) InternalHigh: usize,
+        pub(crate) Offset: c_uint,
+        pub(crate) OffsetHigh: c_uint,
+        pub(crate) hEvent: *mut c_void,
+    }
+
+    #[cfg(windows)]
+    impl Default for OVERLAPPED {
+        fn default() -> Self {
+            Self {
+                Internal: 0,
+                InternalHigh: 0,
+                Offset: 0,
+                OffsetHigh: 0,
+                hEvent: std::ptr::null_mut(),
+            }
+        }
+    }
+
+    #[allow(non_snake_case, clippy::upper_case_acronyms)]
+    #[repr(C)]
+    #[derive(Debug, Default, Clone)]
+    pub(crate) struct GUID {
+        pub(crate) Data1: c_uint,
+        pub(crate) Data2: c_ushort,
+        pub(crate) Data3: c_ushort,
+        pub(crate) Data4: [c_uchar; 8],
+    }
+
+    #[allow(non_snake_case)]
+    #[repr(C)]
+    #[derive(Debug, Default, Clone)]
+    pub(crate) struct FT_CONFIGURATION_DESCRIPTOR {
+        pub(crate) bLength: c_uchar,
+        pub(crate) bDescriptorType: c_uchar,
+        pub(crate) wTotalLength: c_ushort,
+        pub(crate) bNumInterfaces: c_uchar,
+        pub(crate) bConfigurationValue: c_uchar,
+        pub(crate) iConfiguration: c_uchar,
+        pub(crate) bmAttributes: c_uchar,
+        pub(crate) MaxPower: c_uchar,
+    }
+
+    #[allow(non_snake_case)]
+    #[repr(C)]
+    #[derive(Debug, Default, Clone)]
+    pub(crate) struct FT_INTERFACE_DESCRIPTOR {
+        pub(crate) bLength: c_uchar,
+        pub(crate) bDescriptorType: c_uchar,
+        pub(crate) bInterfaceNumber: c_uchar,
+        pub(crate) bAlternateSetting: c_uchar,
+        pub(crate) bNumEndpoints: c_uchar,
+        pub(crate) bInterfaceClass: c_uchar,
+        pub(crate) bInterfaceSubClass: c_uchar,
+        pub(crate) bInterfaceProtocol: c_uchar,
+        pub(crate) iInterface: c_uchar,
+    }
+
     #[allow(non_snake_case)]
     #[repr(C)]
     #[derive(Clone)]
@@ -285,6 +443,66 @@ pub mod types {
         pub(crate) Interval: c_uchar,
     }
 
+    /// Chip configuration of an FT60x device. This is a fixed 152 byte layout,
+    /// so the 32-bit fields are `u32` rather than `c_ulong`.
+    #[allow(non_snake_case)]
+    #[repr(C)]
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    pub(crate) struct FT_60XCONFIGURATION {
+        pub(crate) VendorID: c_ushort,
+        pub(crate) ProductID: c_ushort,
+        pub(crate) StringDescriptors: [c_uchar; 128],
+        pub(crate) Reserved: c_uchar,
+        pub(crate) PowerAttributes: c_uchar,
+        pub(crate) PowerConsumption: c_ushort,
+        pub(crate) Reserved2: c_uchar,
+        pub(crate) FIFOClock: c_uchar,
+        pub(crate) FIFOMode: c_uchar,
+        pub(crate) ChannelConfig: c_uchar,
+        pub(crate) OptionalFeatureSupport: c_ushort,
+        pub(crate) BatteryChargingGPIOConfig: c_uchar,
+        pub(crate) FlashEEPROMDetection: c_uchar,
+        pub(crate) MSIO_Control: u32,
+        pub(crate) GPIO_Control: u32,
+    }
+
+    impl Default for FT_60XCONFIGURATION {
+        fn default() -> Self {
+            unsafe { std::mem::zeroed() }
+        }
+    }
+
+    impl Debug for FT_60XCONFIGURATION {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("FT_60XCONFIGURATION")
+                .field("VendorID", &self.VendorID)
+                .field("ProductID", &self.ProductID)
+                .field("StringDescriptors", &&self.StringDescriptors[..])
+                .field("PowerAttributes", &self.PowerAttributes)
+                .field("PowerConsumption", &self.PowerConsumption)
+                .field("FIFOClock", &self.FIFOClock)
+                .field("FIFOMode", &self.FIFOMode)
+                .field("ChannelConfig", &self.ChannelConfig)
+                .field("OptionalFeatureSupport", &self.OptionalFeatureSupport)
+                .field("BatteryChargingGPIOConfig", &self.BatteryChargingGPIOConfig)
+                .field("FlashEEPROMDetection", &self.FlashEEPROMDetection)
+                .field("MSIO_Control", &self.MSIO_Control)
+                .field("GPIO_Control", &self.GPIO_Control)
+                .finish()
+        }
+    }
+
+    #[allow(non_snake_case)]
+    #[repr(C)]
+    #[derive(Debug, Default, Clone, Copy)]
+    pub(crate) struct FT_SETUP_PACKET {
+        pub(crate) RequestType: c_uchar,
+        pub(crate) Request: c_uchar,
+        pub(crate) Value: c_ushort,
+        pub(crate) Index: c_ushort,
+        pub(crate) Length: c_ushort,
+    }
+
     #[allow(non_camel_case_types)]
     pub(crate) type FT_STATUS = c_ulong;
     #[allow(non_camel_case_types)]
@@ -295,7 +513,7 @@ pub mod types {
         unsafe {
             Ok(CStr::from_ptr(s.as_ptr() as *const _)
                 .to_str()
-                .or(Err(D3xxError::OtherError))?
+                .or(Err(D3xxError::InvalidFrame("string is not valid UTF-8")))?
                 .to_string())
         }
     }
@@ -309,10 +527,15 @@ pub mod constants {
     use libc::{c_uchar, c_ulong, c_ushort};
 
     // Standard Descriptor Types
-    pub(crate) const FT_DEVICE_DESCRIPTOR_TYPE: c_ushort = 0x01;
-    pub(crate) const FT_CONFIGURATION_DESCRIPTOR_TYPE: c_ushort = 0x02;
-    pub(crate) const FT_STRING_DESCRIPTOR_TYPE: c_ushort = 0x03;
-    pub(crate) const FT_INTERFACE_DESCRIPTOR_TYPE: c_ushort = 0x04;
+    pub(crate) const FT_DEVICE_DESCRIPTOR_TYPE: c_uchar = 0x01;
+    pub(crate) const FT_CONFIGURATION_DESCRIPTOR_TYPE: c_uchar = 0x02;
+    pub(crate) const FT_STRING_DESCRIPTOR_TYPE: c_uchar = 0x03;
+    pub(crate) const FT_INTERFACE_DESCRIPTOR_TYPE: c_uchar = 0x04;
+
+    // Further descriptor types from the USB 3.2 specification, not in FTD3XX.h
+    pub(crate) const FT_ENDPOINT_DESCRIPTOR_TYPE: c_uchar = 0x05;
+    pub(crate) const FT_BOS_DESCRIPTOR_TYPE: c_uchar = 0x0F;
+    pub(crate) const FT_SUPERSPEED_ENDPOINT_COMPANION_DESCRIPTOR_TYPE: c_uchar = 0x30;
 
     // Reserved pipes
     pub(crate) const FT_RESERVED_INTERFACE_INDEX: c_ushort = 0x0;
@@ -333,6 +556,11 @@ pub mod constants {
     pub(crate) const FT_LIST_BY_INDEX: c_ulong = 0x40000000;
     pub(crate) const FT_LIST_NUMBER_ONLY: c_ulong = 0x80000000;
 
+    // Device list info flags
+    pub(crate) const FT_FLAGS_OPENED: c_ulong = 0x00000001;
+    pub(crate) const FT_FLAGS_HISPEED: c_ulong = 0x00000002;
+    pub(crate) const FT_FLAGS_SUPERSPEED: c_ulong = 0x00000004;
+
     // GPIO direction, value
     pub(crate) const FT_GPIO_DIRECTION_IN: c_uchar = 0;
     pub(crate) const FT_GPIO_DIRECTION_OUT: c_uchar = 1;
@@ -340,4 +568,43 @@ pub mod constants {
     pub(crate) const FT_GPIO_VALUE_HIGH: c_uchar = 1;
     pub(crate) const FT_GPIO_0: c_uchar = 0;
     pub(crate) const FT_GPIO_1: c_uchar = 1;
+
+    // Chip configuration FIFO clock
+    pub(crate) const CONFIGURATION_FIFO_CLK_100: c_uchar = 0;
+    pub(crate) const CONFIGURATION_FIFO_CLK_66: c_uchar = 1;
+    pub(crate) const CONFIGURATION_FIFO_CLK_50: c_uchar = 2;
+    pub(crate) const CONFIGURATION_FIFO_CLK_40: c_uchar = 3;
+
+    // Chip configuration FIFO mode
+    pub(crate) const CONFIGURATION_FIFO_MODE_245: c_uchar = 0;
+    pub(crate) const CONFIGURATION_FIFO_MODE_600: c_uchar = 1;
+
+    // Chip configuration channel configuration
+    pub(crate) const CONFIGURATION_CHANNEL_CONFIG_4: c_uchar = 0;
+    pub(crate) const CONFIGURATION_CHANNEL_CONFIG_2: c_uchar = 1;
+    pub(crate) const CONFIGURATION_CHANNEL_CONFIG_1: c_uchar = 2;
+    pub(crate) const CONFIGURATION_CHANNEL_CONFIG_1_OUTPIPE: c_uchar = 3;
+    pub(crate) const CONFIGURATION_CHANNEL_CONFIG_1_INPIPE: c_uchar = 4;
+
+    // Chip configuration optional features
+    pub(crate) const CONFIGURATION_OPTIONAL_FEATURE_ENABLEBATTERYCHARGING: c_ushort = 0x1 << 0;
+    pub(crate) const CONFIGURATION_OPTIONAL_FEATURE_DISABLECANCELSESSIONUNDERRUN: c_ushort =
+        0x1 << 1;
+    pub(crate) const CONFIGURATION_OPTIONAL_FEATURE_ENABLENOTIFICATIONMESSAGE_INCH1: c_ushort =
+        0x1 << 2;
+    pub(crate) const CONFIGURATION_OPTIONAL_FEATURE_DISABLEUNDERRUN_INCH1: c_ushort = 0x1 << 6;
+    pub(crate) const CONFIGURATION_OPTIONAL_FEATURE_SUPPORT_ENABLE_FIFO_IN_SUSPEND: c_ushort =
+        0x1 << 10;
+    pub(crate) const CONFIGURATION_OPTIONAL_FEATURE_SUPPORT_DISABLE_CHIP_POWERDOWN: c_ushort =
+        0x1 << 11;
+
+    // Chip configuration flash/EEPROM detection bit positions
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_ROM: c_uchar = 0;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_MEMORY_NOTEXIST: c_uchar = 1;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_CUSTOMDATA_INVALID: c_uchar = 2;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_CUSTOMDATACHKSUM_INVALID: c_uchar = 3;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_CUSTOM: c_uchar = 4;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_GPIO_INPUT: c_uchar = 5;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_GPIO_0: c_uchar = 6;
+    pub(crate) const CONFIGURATION_FLASH_ROM_BIT_GPIO_1: c_uchar = 7;
 }
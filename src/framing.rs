@@ -0,0 +1,151 @@
+//! A simple packet framing protocol on top of raw pipe I/O.
+//!
+//! Each packet is sent as a single transfer holding a fixed header followed by
+//! the payload:
+//!
+//! ```text
+//! [u32 magic][u32 sequence][u32 payload length][payload bytes]
+//! ```
+
+use crate::{D3xxError, Device, PipePair, Result};
+
+/// Size of the packet header in bytes.
+const HEADER_SIZE: usize = 12;
+
+/// The byte order of the header fields.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ByteOrder {
+    /// Least significant byte first.
+    #[default]
+    LittleEndian,
+    /// Most significant byte first.
+    BigEndian,
+}
+
+impl ByteOrder {
+    fn encode(&self, value: u32) -> [u8; 4] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> u32 {
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        match self {
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        }
+    }
+}
+
+/// Format of the packets handled by a [`PacketFramer`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PacketFramerConfig {
+    /// Value identifying the start of a packet.
+    pub magic: u32,
+    /// Byte order of the header fields.
+    pub byte_order: ByteOrder,
+    /// Largest payload accepted when receiving, in bytes.
+    pub max_payload_len: u32,
+}
+
+impl Default for PacketFramerConfig {
+    fn default() -> Self {
+        Self {
+            magic: 0xF7A3_5A11,
+            byte_order: ByteOrder::LittleEndian,
+            max_payload_len: 1024 * 1024,
+        }
+    }
+}
+
+/// Sends and receives framed packets over a [`PipePair`].
+///
+/// ```no_run
+/// use ft60x_rs::{Device, PacketFramer, PacketFramerConfig, PipePair};
+///
+/// let device = Device::open_by_index(0)?;
+/// let mut framer = PacketFramer::new(PipePair::default_pair(), PacketFramerConfig::default());
+/// framer.send(&device, b"hello")?;
+/// let mut reply = Vec::new();
+/// framer.receive(&device, &mut reply)?;
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PacketFramer {
+    pair: PipePair,
+    config: PacketFramerConfig,
+    next_sequence: u32,
+}
+
+impl PacketFramer {
+    /// Create a new framer using the given pipes and packet format.
+    pub fn new(pair: PipePair, config: PacketFramerConfig) -> PacketFramer {
+        Self {
+            pair,
+            config,
+            next_sequence: 0,
+        }
+    }
+
+    /// The pipes packets are sent and received on.
+    pub fn pair(&self) -> PipePair {
+        self.pair
+    }
+
+    /// The packet format.
+    pub fn config(&self) -> &PacketFramerConfig {
+        &self.config
+    }
+
+    /// Send a packet holding the given payload, returning its sequence number.
+    ///
+    /// # Errors
+    /// - [`D3xxError::InvalidParameter`] if the payload is longer than `u32::MAX` bytes.
+    /// - Any error from [`Device::write_all`].
+    pub fn send(&mut self, device: &Device, payload: &[u8]) -> Result<u64> {
+        let len = u32::try_from(payload.len()).or(Err(D3xxError::InvalidParameter))?;
+        let sequence = self.next_sequence;
+        let order = self.config.byte_order;
+
+        let mut packet = Vec::with_capacity(HEADER_SIZE + payload.len());
+        packet.extend_from_slice(&order.encode(self.config.magic));
+        packet.extend_from_slice(&order.encode(sequence));
+        packet.extend_from_slice(&order.encode(len));
+        packet.extend_from_slice(payload);
+        device.write_all(self.pair.output, &packet)?;
+
+        self.next_sequence = sequence.wrapping_add(1);
+        Ok(sequence as u64)
+    }
+
+    /// Receive a packet, replacing the contents of `buf` with its payload and
+    /// returning its sequence number.
+    ///
+    /// # Errors
+    /// - [`D3xxError::InvalidFrame`] if the magic value does not match, the
+    ///   payload is longer than allowed, or the packet is truncated.
+    /// - Any error from [`Device::read_exact`].
+    pub fn receive(&mut self, device: &Device, buf: &mut Vec<u8>) -> Result<u64> {
+        let order = self.config.byte_order;
+        let mut header = [0; HEADER_SIZE];
+        device.read_exact(self.pair.input, &mut header)?;
+        if order.decode(&header[0..4]) != self.config.magic {
+            Err(D3xxError::InvalidFrame("magic mismatch"))?;
+        }
+        let sequence = order.decode(&header[4..8]);
+        let len = order.decode(&header[8..12]);
+        if len > self.config.max_payload_len {
+            Err(D3xxError::InvalidFrame("payload too long"))?;
+        }
+
+        buf.clear();
+        buf.resize(len as usize, 0);
+        match device.read_exact(self.pair.input, buf) {
+            Err(D3xxError::Timeout) => Err(D3xxError::InvalidFrame("truncated packet"))?,
+            result => result?,
+        }
+        Ok(sequence as u64)
+    }
+}
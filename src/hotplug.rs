@@ -0,0 +1,125 @@
+//! Detection of devices being connected or disconnected.
+
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::{list_devices, DeviceInfo};
+
+/// An event reported by a [`HotplugMonitor`].
+#[derive(Debug, Clone)]
+pub enum HotplugEvent {
+    /// A device was connected.
+    DeviceArrived(DeviceInfo),
+    /// A device was disconnected.
+    DeviceRemoved(DeviceInfo),
+}
+
+/// Watches for devices being connected or disconnected by periodically
+/// polling the D3XX device list on a background thread.
+///
+/// Devices are identified by their serial number. The background thread is
+/// stopped when the monitor is dropped.
+#[derive(Debug)]
+pub struct HotplugMonitor {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl HotplugMonitor {
+    /// Start polling the device list every `interval`, calling `callback`
+    /// for each device that arrives or is removed.
+    ///
+    /// Devices already connected when the monitor is started are reported as arrivals.
+    pub fn spawn(
+        interval: Duration,
+        callback: impl Fn(HotplugEvent) + Send + 'static,
+    ) -> HotplugMonitor {
+        let (stop, stop_rx) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            let mut known: Vec<(String, DeviceInfo)> = Vec::new();
+            loop {
+                // Polling failures are transient as far as the monitor is
+                // concerned, so just try again next time.
+                if let Ok(devices) = list_devices() {
+                    let current: Vec<_> = devices
+                        .into_iter()
+                        .filter_map(|info| Some((info.serial_number().ok()?, info)))
+                        .collect();
+                    for (serial, info) in &known {
+                        if !current.iter().any(|(s, _)| s == serial) {
+                            callback(HotplugEvent::DeviceRemoved(info.clone()));
+                        }
+                    }
+                    for (serial, info) in &current {
+                        if !known.iter().any(|(s, _)| s == serial) {
+                            callback(HotplugEvent::DeviceArrived(info.clone()));
+                        }
+                    }
+                    known = current;
+                }
+                match stop_rx.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    _ => break,
+                }
+            }
+        });
+        Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /// Start polling the device list every `interval`, reporting events
+    /// through an asynchronous stream.
+    ///
+    /// Polling stops when the stream is dropped.
+    #[cfg(feature = "tokio")]
+    pub fn stream(interval: Duration) -> impl futures_core::Stream<Item = HotplugEvent> {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let monitor = Self::spawn(interval, move |event| {
+            let _ = tx.send(event);
+        });
+        stream::HotplugStream {
+            _monitor: monitor,
+            rx,
+        }
+    }
+}
+
+impl Drop for HotplugMonitor {
+    fn drop(&mut self) {
+        // Dropping the sender wakes the thread up and tells it to stop.
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod stream {
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use super::{HotplugEvent, HotplugMonitor};
+
+    /// Stream of events from a [`HotplugMonitor`] which it keeps alive.
+    pub(super) struct HotplugStream {
+        /// Only held so that polling continues for as long as the stream exists.
+        pub(super) _monitor: HotplugMonitor,
+        pub(super) rx: tokio::sync::mpsc::UnboundedReceiver<HotplugEvent>,
+    }
+
+    impl futures_core::Stream for HotplugStream {
+        type Item = HotplugEvent;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.rx.poll_recv(cx)
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Identifying a physical device, e.g. in a rack of identical units.
+
+use std::time::{Duration, Instant};
+
+use crate::{Device, GpioDirection, GpioPin, GpioValue, Pipe, Result};
+
+/// A way of making a device visibly identify itself.
+///
+/// Used by [`Device::identify_with`]. Implemented by [`GpioBlink`] and
+/// [`PipeMarker`], and by closures taking the device and the duration, for
+/// application-specific methods:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use ft60x_rs::{Device, Pipe};
+///
+/// let device = Device::open_by_index(0)?;
+/// let blink_command = |device: &Device, _: Duration| device.write_all(Pipe::Out0, &[0xB1; 4]);
+/// device.identify_with(&blink_command, Duration::from_secs(5))?;
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+pub trait IdentificationStrategy {
+    /// Identify the device for roughly the given duration.
+    fn identify(&self, device: &Device, duration: Duration) -> Result<()>;
+}
+
+impl<F> IdentificationStrategy for F
+where
+    F: Fn(&Device, Duration) -> Result<()>,
+{
+    fn identify(&self, device: &Device, duration: Duration) -> Result<()> {
+        self(device, duration)
+    }
+}
+
+/// Toggles a GPIO pin, e.g. one driving an LED, blocking for the whole duration.
+///
+/// The pin is configured as an output while blinking and set back to an
+/// input afterwards. GPIO 0 and GPIO 1 are dedicated pins of the FT600 and
+/// FT601; see the chip datasheet for their package pins, and the module or
+/// board schematic for where they are brought out. The pins are also used
+/// for battery charging detection when it is enabled in the chip
+/// configuration, in which case they cannot be driven.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct GpioBlink {
+    /// The pin to toggle.
+    pub pin: GpioPin,
+    /// The duration of one full on/off cycle.
+    pub period: Duration,
+}
+
+impl Default for GpioBlink {
+    /// Toggle GPIO 0 at 1 Hz.
+    fn default() -> Self {
+        Self {
+            pin: GpioPin::Pin0,
+            period: Duration::from_secs(1),
+        }
+    }
+}
+
+impl IdentificationStrategy for GpioBlink {
+    fn identify(&self, device: &Device, duration: Duration) -> Result<()> {
+        device.set_gpio_direction(self.pin, GpioDirection::Output)?;
+        let deadline = Instant::now() + duration;
+        let mut value = GpioValue::High;
+        let result = loop {
+            if let Err(e) = device.set_gpio_value(self.pin, value) {
+                break Err(e);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break Ok(());
+            }
+            std::thread::sleep(remaining.min(self.period / 2));
+            value = match value {
+                GpioValue::High => GpioValue::Low,
+                GpioValue::Low => GpioValue::High,
+            };
+        };
+        let restored = device.set_gpio_direction(self.pin, GpioDirection::Input);
+        // An error from blinking is more useful than one from restoring.
+        result?;
+        restored
+    }
+}
+
+/// Writes a marker to an OUT pipe once, for gateware which recognizes it and
+/// drives an indicator by itself. The duration is ignored.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PipeMarker {
+    /// The pipe to write the marker to.
+    pub pipe: Pipe,
+    /// The marker to write.
+    pub marker: Vec<u8>,
+}
+
+impl PipeMarker {
+    /// The marker written by [`PipeMarker::default`]: the ASCII bytes `FT60xIDENTIFY!!!`.
+    pub const DEFAULT_MARKER: [u8; 16] = *b"FT60xIDENTIFY!!!";
+}
+
+impl Default for PipeMarker {
+    /// Write [`PipeMarker::DEFAULT_MARKER`] to `Out0`.
+    fn default() -> Self {
+        Self {
+            pipe: Pipe::Out0,
+            marker: Self::DEFAULT_MARKER.to_vec(),
+        }
+    }
+}
+
+impl IdentificationStrategy for PipeMarker {
+    fn identify(&self, device: &Device, _duration: Duration) -> Result<()> {
+        device.write_all(self.pipe, &self.marker)
+    }
+}
@@ -10,21 +10,157 @@
 //! The D3XX library must be loaded before any D3XX functions can be called.
 //! This can be done by calling [`load_dylib`] to load an external library from disk
 //! or [`load_bundled_dylib`] to use the bundled library for the current platform.
+//!
+//! # Platform support
+//!
+//! The D3XX library is always loaded at runtime, so no linker configuration is
+//! needed on Windows (`FTD3XX.dll`), Linux (`libftd3xx.so`) or macOS
+//! (`libftd3xx.dylib`). The bundled libraries are built for x86-64 Windows and
+//! Linux only; on other targets, such as macOS or a Raspberry Pi, download the
+//! library for that platform from FTDI and load it with [`load_dylib`].
+//!
+//! # Opening devices
+//!
+//! Besides the `Device::open*` constructors, a device can be opened by
+//! converting from its [`DeviceInfo`] or its serial number:
+//!
+//! ```no_run
+//! use ft60x_rs::Device;
+//!
+//! let info = &ft60x_rs::list_devices()?[0];
+//! let device: Device = info.try_into()?;
+//! # drop(device);
+//! let device: Device = "000000000001".try_into()?;
+//! # Ok::<(), ft60x_rs::D3xxError>(())
+//! ```
+//!
+//! # Multithreading
+//!
+//! A [`Device`] may be moved to another thread, but it must not be used from several
+//! threads at once. To share a device, wrap it in a mutex:
+//!
+//! ```no_run
+//! use std::sync::{Arc, Mutex};
+//! use ft60x_rs::{Device, Pipe};
+//!
+//! ft60x_rs::load_bundled_dylib()?;
+//! let device = Arc::new(Mutex::new(Device::open_by_index(0)?));
+//!
+//! let writer = {
+//!     let device = Arc::clone(&device);
+//!     std::thread::spawn(move || device.lock().unwrap().write(Pipe::Out0, &[0; 16]))
+//! };
+//! let mut buf = [0; 16];
+//! device.lock().unwrap().read(Pipe::In0, &mut buf)?;
+//! writer.join().unwrap()?;
+//! # Ok::<(), ft60x_rs::D3xxError>(())
+//! ```
+//!
+//! [`ThreadSafeDevice`] provides the same thing with the locking taken care of.
+//!
+//! # Tracing
+//!
+//! With the `tracing` feature enabled, reads and writes are wrapped in
+//! `d3xx::read`/`d3xx::write` debug spans recording the pipe, the buffer length,
+//! and the outcome of the transfer. Listing devices is wrapped in an info span.
+//! Attach any subscriber to see them, for example using `tracing-subscriber`:
+//!
+//! ```ignore
+//! tracing_subscriber::fmt()
+//!     .with_max_level(tracing::Level::DEBUG)
+//!     .init();
+//! ```
 
 pub(crate) mod assets;
+#[cfg(feature = "tokio")]
+pub(crate) mod async_io;
+pub(crate) mod buffer;
+pub(crate) mod builder;
+pub(crate) mod config;
 pub mod error;
+pub(crate) mod exclusive;
 pub(crate) mod ffi;
-
-use std::{ffi::CString, fmt::Debug, ptr::null_mut, time::Duration};
+pub(crate) mod framing;
+pub(crate) mod hotplug;
+pub(crate) mod identify;
+pub(crate) mod multi_pipe;
+pub(crate) mod notification;
+#[cfg(windows)]
+pub(crate) mod overlapped;
+pub(crate) mod pipe_io;
+pub(crate) mod pool;
+pub(crate) mod reconnect;
+pub(crate) mod retry;
+#[cfg(feature = "ring-buffer")]
+pub(crate) mod ring;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub(crate) mod slot;
+pub(crate) mod snapshot;
+#[cfg(feature = "stats")]
+pub(crate) mod stats;
+pub(crate) mod sync;
+pub(crate) mod transfer;
+
+use std::{
+    cell::Cell,
+    ffi::CString,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    ops::{BitOr, BitOrAssign},
+    str::FromStr,
+    time::Duration,
+};
 
 use ffi::{constants, lib, ptr_mut, types};
 use libc::{c_uchar, c_ulong, c_ushort, c_void};
 
-pub use error::D3xxError;
+#[cfg(feature = "tokio")]
+pub use async_io::{AsyncDevice, AsyncPipeReader, AsyncPipeWriter};
+pub use buffer::AlignedBuffer;
+pub use builder::DeviceBuilder;
+pub use config::{
+    ChannelConfig, ChipConfiguration, ChipConfigurationBuilder, FifoClock, FifoMode,
+    FlashEepromDetection, OptionalFeatures,
+};
+pub use error::{
+    AllocationError, D3xxError, D3xxErrorContext, ErrorCategory, ParsePipeError, ParseVersionError,
+    ReadWriteError,
+};
 pub use assets::{load_dylib, load_bundled_dylib};
+pub use exclusive::ExclusiveDevice;
+pub use framing::{ByteOrder, PacketFramer, PacketFramerConfig};
+pub use hotplug::{HotplugEvent, HotplugMonitor};
+pub use identify::{GpioBlink, IdentificationStrategy, PipeMarker};
+pub use multi_pipe::{MultiPipeReader, PipeData};
+pub use notification::NotificationListener;
+#[cfg(windows)]
+pub use overlapped::Transfer;
+pub use pipe_io::{
+    ChunkedPipeReader, FramedReader, FramedWriter, PipeReader, PipeWriter, RateLimitedWriter,
+};
+pub use pool::{DeviceGuard, DevicePool};
+pub use reconnect::{ConnectionEvent, ReconnectPolicy, ReconnectingDevice};
+pub use retry::RetryPolicy;
+#[cfg(feature = "ring-buffer")]
+pub use ring::RingPipeReader;
+pub use slot::DeviceSlot;
+pub use snapshot::DeviceSnapshot;
+#[cfg(feature = "stats")]
+pub use stats::TransferStats;
+pub use sync::ThreadSafeDevice;
 
 pub type Result<T, E = D3xxError> = std::result::Result<T, E>;
 
+/// The shortest pipe timeout, used for non-blocking transfers.
+const NON_BLOCKING_TIMEOUT: Duration = Duration::from_millis(1);
+
+/// The pipe timeout D3XX uses for a newly opened device, in milliseconds.
+const DEFAULT_PIPE_TIMEOUT_MS: u32 = 5000;
+
+/// The language ID for English (United States), used by default for string descriptors.
+pub const LANGUAGE_ID_ENGLISH_US: u16 = 0x0409;
+
 /// A D3XX device.
 ///
 /// The [`Device`] struct represents a single D3XX device. It provides methods
@@ -33,9 +169,29 @@ pub type Result<T, E = D3xxError> = std::result::Result<T, E>;
 pub struct Device {
     /// The raw handle to the D3XX device.
     handle: types::FT_HANDLE,
+    /// Statistics for transfers made using this device.
+    #[cfg(feature = "stats")]
+    stats: TransferStats,
+    /// Stream sizes set on each pipe, as D3XX provides no way to query them.
+    stream_sizes: Cell<[Option<u32>; 8]>,
+    /// Timeouts set on each pipe in milliseconds, as not every D3XX library
+    /// provides a way to query them.
+    timeouts: Cell<[u32; 8]>,
+    /// Packet sizes that transfers on each pipe must be a multiple of, or zero
+    /// if alignment enforcement is disabled for the pipe.
+    enforced_alignments: Cell<[u16; 8]>,
+    /// Maximum packet sizes of each pipe once queried, or zero if not yet known.
+    packet_sizes: Cell<[u16; 8]>,
+    /// Cached index in the D3XX device list.
+    index: Cell<Option<usize>>,
 }
 
 impl Device {
+    /// Create a [`DeviceBuilder`] for opening and configuring a device.
+    pub fn builder() -> DeviceBuilder {
+        DeviceBuilder::new()
+    }
+
     /// Open a device using the given device information.
     pub fn open(info: &DeviceInfo) -> Result<Device> {
         Self::open_with_serial_number(&info.serial_number()?)
@@ -43,21 +199,131 @@ impl Device {
 
     /// Open a device using the given serial number.
     pub fn open_with_serial_number(serial_number: &str) -> Result<Device> {
+        let serial = CString::new(serial_number).or(Err(D3xxError::InvalidParameter))?;
         unsafe {
-            let serial = CString::new(serial_number).or(Err(D3xxError::InvalidParameter))?;
-            let mut handle: types::FT_HANDLE = std::ptr::null_mut();
-            lib::FT_Create(
+            Self::create(
                 serial.as_ptr() as *mut c_void,
                 constants::FT_OPEN_BY_SERIAL_NUMBER,
-                &mut handle as *mut types::FT_HANDLE,
-            )?;
-            Ok(Self::from_handle(handle))
+            )
+        }
+    }
+
+    /// Open a device using its index in the D3XX device list.
+    pub fn open_by_index(index: usize) -> Result<Device> {
+        let device = unsafe { Self::create(index as *mut c_void, constants::FT_OPEN_BY_INDEX)? };
+        device.index.set(Some(index));
+        Ok(device)
+    }
+
+    /// Open a device using the given product description.
+    pub fn open_by_description(description: &str) -> Result<Device> {
+        let description = CString::new(description).or(Err(D3xxError::InvalidParameter))?;
+        unsafe {
+            Self::create(
+                description.as_ptr() as *mut c_void,
+                constants::FT_OPEN_BY_DESCRIPTION,
+            )
+        }
+    }
+
+    /// Open a device using the given location identifier.
+    pub fn open_by_location(location_id: u32) -> Result<Device> {
+        unsafe {
+            Self::create(
+                location_id as usize as *mut c_void,
+                constants::FT_OPEN_BY_LOCATION,
+            )
         }
     }
 
+    /// The device interface GUID registered by the Windows D3XX driver for
+    /// FT60x devices, for use with [`Device::open_with_guid`].
+    pub const D3XX_INTERFACE_GUID: &'static str = "{D1E8FE6A-AB75-4D9E-97D2-06FA22C7736C}";
+
+    /// Open a device using its device interface GUID, in the format
+    /// `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`.
+    ///
+    /// Unless a custom driver INF assigns a different interface GUID, FT60x
+    /// devices use [`Device::D3XX_INTERFACE_GUID`], and the first such device
+    /// is opened. Only the Windows D3XX library supports opening by GUID.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the GUID is malformed.
+    pub fn open_with_guid(guid: &str) -> Result<Device> {
+        let mut guid = parse_guid(guid).ok_or(D3xxError::InvalidParameter)?;
+        unsafe { Self::create(ptr_mut(&mut guid), constants::FT_OPEN_BY_GUID) }
+    }
+
+    /// Open the first device which is not already open.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::DeviceNotFound`] if there is no device available.
+    pub fn open_any() -> Result<Device> {
+        Self::open_any_matching(|_| true)
+    }
+
+    /// Open the first device which is not already open and matches the given predicate.
+    ///
+    /// If another process opens a device between listing and opening it, the
+    /// next matching device is tried instead.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::DeviceNotFound`] if there is no matching device available.
+    pub fn open_any_matching(predicate: impl Fn(&DeviceInfo) -> bool) -> Result<Device> {
+        for info in list_devices()? {
+            if info.is_open() || !predicate(&info) {
+                continue;
+            }
+            match Self::open(&info) {
+                Err(D3xxError::DeviceNotFound | D3xxError::DeviceNotOpened) => continue,
+                result => return result,
+            }
+        }
+        Err(D3xxError::DeviceNotFound)
+    }
+
+    /// Open a device using the given serial number, holding a system-wide lock
+    /// so no other process using this crate can open it at the same time.
+    ///
+    /// On Windows the lock is a named mutex, and on other platforms it is a
+    /// lock file under `/tmp/ft60x-<serial>.lock`. The lock does not prevent
+    /// processes using other drivers or libraries from opening the device.
+    ///
+    /// # Errors
+    /// - [`D3xxError::Busy`] if the lock is already held.
+    /// - [`D3xxError::InvalidParameter`] if the serial number cannot be used
+    ///   to name the lock.
+    /// - Any error from [`Device::open_with_serial_number`].
+    pub fn open_exclusive(serial_number: &str) -> Result<ExclusiveDevice> {
+        ExclusiveDevice::open(serial_number)
+    }
+
+    /// Open a device by calling `FT_Create` with the given argument and flags.
+    ///
+    /// # Safety
+    /// `arg` must be valid for the given open flags.
+    unsafe fn create(arg: *mut c_void, flags: c_ulong) -> Result<Device> {
+        let mut handle: types::FT_HANDLE = std::ptr::null_mut();
+        lib::FT_Create(arg, flags, &mut handle as *mut types::FT_HANDLE)?;
+        Ok(Self::from_handle(handle))
+    }
+
     /// Create a device wrapper using a raw handle
+    ///
+    /// # Safety
+    /// The handle must be a valid handle obtained from `FT_Create`. The returned
+    /// [`Device`] takes ownership of the handle and closes it when dropped.
     pub unsafe fn from_handle(handle: types::FT_HANDLE) -> Device {
-        Self { handle }
+        Self {
+            handle,
+            #[cfg(feature = "stats")]
+            stats: TransferStats::default(),
+            stream_sizes: Cell::default(),
+            timeouts: Cell::new([DEFAULT_PIPE_TIMEOUT_MS; 8]),
+            enforced_alignments: Cell::default(),
+            packet_sizes: Cell::default(),
+            index: Cell::default(),
+        }
     }
 
     /// Get the raw handle to the D3XX device.
@@ -65,23 +331,31 @@ impl Device {
         self.handle
     }
 
+    /// Look up information about the device with the given raw handle.
+    ///
+    /// See [`DeviceInfo::from_handle`].
+    pub fn info_from_raw_handle(handle: types::FT_HANDLE) -> Result<DeviceInfo> {
+        DeviceInfo::from_handle(handle)
+    }
+
     /// Gets information about the device.
+    ///
+    /// The D3XX device list is rebuilt first, so the information is current.
+    /// If the cached index no longer refers to this device, for example after
+    /// devices were connected or disconnected, the device is looked up again
+    /// by its handle and the cache is updated.
     pub fn info(&self) -> Result<DeviceInfo> {
-        let index = self.index()?;
-        let mut device_info = types::FT_DEVICE_LIST_INFO_NODE::default();
-        unsafe {
-            lib::FT_GetDeviceInfoDetail(
-                index as c_ulong,
-                ptr_mut(&mut device_info.Flags),
-                ptr_mut(&mut device_info.Type),
-                ptr_mut(&mut device_info.ID),
-                ptr_mut(&mut device_info.LocId),
-                ptr_mut(&mut device_info.SerialNumber),
-                ptr_mut(&mut device_info.Description),
-                ptr_mut(&mut device_info.ftHandle),
-            )?;
+        device_count()?;
+        if let Some(index) = self.index.get() {
+            if let Ok(info) = DeviceInfo::from_index(index) {
+                if info.raw_handle() == Some(self.handle) {
+                    return Ok(info);
+                }
+            }
         }
-        Ok(DeviceInfo::new(index, device_info))
+        let info = DeviceInfo::from_handle(self.handle)?;
+        self.index.set(Some(info.index));
+        Ok(info)
     }
 
     /// Get the vendor ID of the device.
@@ -114,6 +388,16 @@ impl Device {
         Ok(self.device_descriptor()?.is_usb3())
     }
 
+    /// Check if the device is connected at Hi-Speed (USB 2.0).
+    pub fn is_high_speed(&self) -> Result<bool> {
+        Ok(self.info()?.is_high_speed())
+    }
+
+    /// Get the speed the device is connected at.
+    pub fn speed(&self) -> Result<DeviceSpeed> {
+        Ok(self.info()?.speed())
+    }
+
     /// Gets the D3XX kernel driver version.
     pub fn driver_version(&self) -> Result<Version> {
         let mut version: c_ulong = 0;
@@ -124,71 +408,225 @@ impl Device {
     }
 
     /// Get the index of this device in the current device info list.
+    ///
+    /// The index is looked up once and then cached. Call
+    /// [`Device::invalidate_index_cache`] after devices are connected or
+    /// disconnected, as that may change the index.
     pub fn index(&self) -> Result<usize> {
-        let devices = list_devices()?;
-        let (i, _) = devices
-            .iter()
-            .enumerate()
-            .find(|(_, x)| match x.raw_handle() {
-                Some(handle) => handle == self.handle,
-                None => false,
-            })
-            .ok_or(D3xxError::DeviceNotFound)?;
+        if let Some(index) = self.index.get() {
+            return Ok(index);
+        }
+        let i = DeviceInfo::from_handle(self.handle)?.index;
+        self.index.set(Some(i));
         Ok(i)
     }
 
+    /// Forget the cached device index, so that the next call to
+    /// [`Device::index`] looks it up again.
+    pub fn invalidate_index_cache(&self) {
+        self.index.set(None);
+    }
+
     /// Get information about a pipe.
-    #[allow(unused, unreachable_code)]
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the device does not expose the pipe.
     pub fn pipe_info(&self, pipe: Pipe) -> Result<PipeInfo> {
-        todo!("this doesn't work, interface index is wrong");
+        self.pipe_info_all()?
+            .get(pipe)
+            .copied()
+            .ok_or(D3xxError::InvalidParameter)
+    }
 
-        let mut info = PipeInfo::default();
-        unsafe {
-            lib::FT_GetPipeInformation(self.handle, 0, pipe as c_uchar, ptr_mut(&mut info))?;
+    /// Get information about all pipes exposed by the device.
+    ///
+    /// Pipes which the device does not expose, for example due to its channel
+    /// configuration, are absent from the returned map.
+    pub fn pipe_info_all(&self) -> Result<PipeInfoMap> {
+        // Only SuperSpeed devices have companion descriptors, so failing to
+        // read them just leaves them absent.
+        let companions = self.endpoint_companions().unwrap_or_default();
+        self.query_pipe_info(&companions)
+    }
+
+    /// Get information about all data pipes, attaching the given endpoint
+    /// companion descriptors. See [`Device::endpoint_companions`].
+    fn query_pipe_info(&self, companions: &[(u8, u8, u8)]) -> Result<PipeInfoMap> {
+        // Data pipes live on the second interface; the first only holds the
+        // session and notification pipes used internally by D3XX.
+        const DATA_INTERFACE: c_uchar = 1;
+
+        let mut map = PipeInfoMap::default();
+        for index in 0..PipeInfoMap::CAPACITY as c_uchar {
+            let mut info = PipeInfo::default();
+            let result = unsafe {
+                lib::FT_GetPipeInformation(
+                    self.handle,
+                    DATA_INTERFACE,
+                    index,
+                    ptr_mut(&mut info.inner),
+                )
+            };
+            match result {
+                Ok(()) => (),
+                Err(D3xxError::NotSupported | D3xxError::InvalidParameter) => break,
+                Err(e) => Err(e)?,
+            }
+            if let Ok(pipe) = info.pipe() {
+                info.companion = companions
+                    .iter()
+                    .find(|(address, ..)| *address == pipe.to_endpoint_address())
+                    .map(|&(_, max_burst, attributes)| (max_burst, attributes));
+                map.pipes[pipe.slot()] = Some(info);
+            }
         }
-        Ok(info)
+        Ok(map)
+    }
+
+    /// Read the SuperSpeed endpoint companion descriptors from the configuration
+    /// descriptor, as `(endpoint address, bMaxBurst, bmAttributes)`.
+    fn endpoint_companions(&self) -> Result<Vec<(u8, u8, u8)>> {
+        let buf = self.raw_configuration_descriptor()?;
+        let mut companions = Vec::new();
+        let mut endpoint = None;
+        let mut rest = buf.as_slice();
+        while let [len, type_, ..] = *rest {
+            let Some(descriptor) = rest.get(..len as usize).filter(|_| len >= 2) else {
+                break;
+            };
+            match (type_, descriptor) {
+                (constants::FT_ENDPOINT_DESCRIPTOR_TYPE, [_, _, address, ..]) => {
+                    endpoint = Some(*address)
+                }
+                (
+                    constants::FT_SUPERSPEED_ENDPOINT_COMPANION_DESCRIPTOR_TYPE,
+                    [_, _, max_burst, attributes, ..],
+                ) => {
+                    if let Some(address) = endpoint.take() {
+                        companions.push((address, *max_burst, *attributes));
+                    }
+                }
+                _ => (),
+            }
+            rest = &rest[len as usize..];
+        }
+        Ok(companions)
+    }
+
+    /// Read the full configuration descriptor, including the interface,
+    /// endpoint and other descriptors following it.
+    fn raw_configuration_descriptor(&self) -> Result<Vec<u8>> {
+        const TYPE: u8 = constants::FT_CONFIGURATION_DESCRIPTOR_TYPE;
+
+        let mut header = [0u8; 9];
+        self.raw_descriptor(TYPE, 0, &mut header)?;
+        let mut buf = vec![0u8; u16::from_le_bytes([header[2], header[3]]) as usize];
+        let n = self.raw_descriptor(TYPE, 0, &mut buf)?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
+    /// Get the pipes exposed by the device, IN pipes first.
+    ///
+    /// An FT600 exposes up to two pipes in each direction, and an FT601 up to
+    /// four, depending on the channel configuration.
+    pub fn available_pipes(&self) -> Result<Vec<Pipe>> {
+        Ok(self
+            .pipe_info_all()?
+            .iter()
+            .filter_map(|info| info.pipe().ok())
+            .collect())
+    }
+
+    /// Get the number of IN pipes exposed by the device.
+    pub fn num_read_pipes(&self) -> Result<usize> {
+        Ok(self
+            .available_pipes()?
+            .iter()
+            .filter(|pipe| pipe.is_read_pipe())
+            .count())
+    }
+
+    /// Get the number of OUT pipes exposed by the device.
+    pub fn num_write_pipes(&self) -> Result<usize> {
+        Ok(self
+            .available_pipes()?
+            .iter()
+            .filter(|pipe| pipe.is_write_pipe())
+            .count())
+    }
+
+    /// Determine whether the device is an FT600 or an FT601.
+    ///
+    /// The device type reported in the device list is used first, then the
+    /// product ID, and finally the number of pipes exposed.
+    pub fn variant(&self) -> Result<DeviceVariant> {
+        let variant = DeviceVariant::from_device_type(self.info()?.type_());
+        if variant != DeviceVariant::Unknown {
+            return Ok(variant);
+        }
+        let variant = DeviceVariant::from_product_id(self.device_descriptor()?.product_id() as u16);
+        if variant != DeviceVariant::Unknown {
+            return Ok(variant);
+        }
+        // Only the FT601 can expose more than two pipes in a direction, but
+        // an FT601 configured with fewer channels looks like an FT600.
+        let (in_count, out_count) = (self.num_read_pipes()?, self.num_write_pipes()?);
+        Ok(match in_count.max(out_count) {
+            3.. => DeviceVariant::FT601,
+            _ => DeviceVariant::Unknown,
+        })
     }
 
     /// Writes data to the specified pipe. This method will block
     /// until the transfer is complete, or the timeout is reached.
+    ///
+    /// If alignment enforcement is enabled for the pipe, the buffer is checked
+    /// as in [`Device::write_aligned`]; see [`Device::set_alignment_enforcement`].
     pub fn write(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
-        if !pipe.is_write_pipe() {
-            Err(D3xxError::InvalidParameter)?;
-        }
-
-        let mut bytes_transferred = 0;
-        unsafe {
-            match lib::FT_WritePipeEx(
-                self.handle,
-                0x02,
-                buf as *const _ as *const u8,
-                buf.len() as c_ulong,
-                &mut bytes_transferred,
-                std::ptr::null_mut(),
-            ) {
-                Ok(_) => (),
-                Err(e) => {
-                    self.abort_transfers(pipe)?;
-                    return Err(e);
-                }
-            }
-        }
-        Ok(bytes_transferred as usize)
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("d3xx::write", pipe = ?pipe, len = buf.len()).entered();
+        let result = self.write_pipe(pipe, buf);
+        #[cfg(feature = "stats")]
+        self.stats.record_write(&result);
+        #[cfg(feature = "tracing")]
+        trace_transfer(&result);
+        result
     }
 
     /// Reads data from the specified pipe. This method will block
     /// until the transfer is complete, or the timeout is reached.
+    ///
+    /// For best throughput, use buffers sized with
+    /// [`PipeInfo::optimal_transfer_size`], or at least a multiple of
+    /// [`PipeInfo::transfer_size_hint`] (see [`PipeInfo::round_up_to_packet`]).
+    ///
+    /// If alignment enforcement is enabled for the pipe, the buffer is checked
+    /// as in [`Device::read_aligned`]; see [`Device::set_alignment_enforcement`].
     pub fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
-        if !pipe.is_read_pipe() {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("d3xx::read", pipe = ?pipe, len = buf.len()).entered();
+        let result = self.read_pipe(pipe, buf);
+        #[cfg(feature = "stats")]
+        self.stats.record_read(&result);
+        #[cfg(feature = "tracing")]
+        trace_transfer(&result);
+        result
+    }
+
+    /// Writes data to the specified pipe using `FT_WritePipeEx`.
+    fn write_pipe(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
+        if !pipe.is_write_pipe() {
             Err(D3xxError::InvalidParameter)?;
         }
+        self.check_enforced_alignment(pipe, buf.len())?;
 
         let mut bytes_transferred = 0;
         unsafe {
-            match lib::FT_ReadPipe(
+            match lib::FT_WritePipeEx(
                 self.handle,
                 pipe as c_uchar,
-                buf as *mut _ as *mut u8,
+                buf as *const _ as *const u8,
                 buf.len() as c_ulong,
                 &mut bytes_transferred,
                 std::ptr::null_mut(),
@@ -203,170 +641,1817 @@ impl Device {
         Ok(bytes_transferred as usize)
     }
 
-    /// Discards any data cached in an IN pipe.
-    /// If `pipe` is an OUT pipe, an `InvalidParameter` error is returned.
-    pub fn flush(&self, pipe: Pipe) -> Result<()> {
+    /// Reads data from the specified pipe using `FT_ReadPipe`.
+    fn read_pipe(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
         if !pipe.is_read_pipe() {
             Err(D3xxError::InvalidParameter)?;
         }
-        unsafe { lib::FT_FlushPipe(self.handle, pipe as c_uchar) }
+        self.check_enforced_alignment(pipe, buf.len())?;
+
+        self.shared_handle().read(pipe, buf)
     }
 
-    /// Configures a timeout for the specified endpoint. Reading and writing will
-    /// timeout in the event the operation hangs for the given duration.
+    /// Writes data to the specified pipe, first checking that the buffer is a
+    /// non-zero multiple of the pipe's maximum packet size.
     ///
-    /// The new value is only valid as long as the device is open; re-opening the device
-    /// will reset the timeout to the default of 5 seconds.
-    pub fn set_timeout(&self, pipe: Pipe, timeout: Duration) -> Result<()> {
-        unsafe {
-            lib::FT_SetPipeTimeout(self.handle, pipe as c_uchar, timeout.as_millis() as c_ulong)
-        }
+    /// Other lengths make the host controller end the transfer with a short
+    /// packet, which some gateware cannot handle.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] without transferring anything if
+    /// the buffer is not aligned. Otherwise, see [`Device::write`].
+    pub fn write_aligned(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
+        check_alignment(buf.len(), self.max_packet_size(pipe)?)?;
+        self.write(pipe, buf)
     }
 
-    /// Get the timeout configured for the specified pipe.
-    pub fn get_timeout(&self, pipe: Pipe) -> Result<Duration> {
-        let mut timeout_millis: c_ulong = 0;
-        unsafe {
-            lib::FT_GetPipeTimeout(self.handle, pipe as c_uchar, ptr_mut(&mut timeout_millis))?;
-        }
-        Ok(Duration::from_millis(timeout_millis as u64))
+    /// Reads data from the specified pipe, first checking that the buffer is a
+    /// non-zero multiple of the pipe's maximum packet size.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] without transferring anything if
+    /// the buffer is not aligned. Otherwise, see [`Device::read`].
+    pub fn read_aligned(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        check_alignment(buf.len(), self.max_packet_size(pipe)?)?;
+        self.read(pipe, buf)
     }
 
-    /// Sets streaming protocol transfer for the specified pipe. This is for
-    /// applications that read or write a fixed size of data to or from the device.
-    pub fn set_stream_size(&self, pipe: Pipe, stream_size: Option<u32>) -> Result<()> {
-        unsafe {
-            match stream_size {
-                Some(size) => lib::FT_SetStreamPipe(
-                    self.handle,
-                    false as c_uchar,
-                    false as c_uchar,
-                    pipe as c_uchar,
-                    size as c_ulong,
-                ),
-                None => lib::FT_ClearStreamPipe(
-                    self.handle,
-                    false as c_uchar,
-                    false as c_uchar,
-                    pipe as c_uchar,
-                ),
-            }
+    /// Make [`Device::read`] and [`Device::write`] reject buffers on the given
+    /// pipe which are not a non-zero multiple of its maximum packet size, as
+    /// [`Device::read_aligned`] and [`Device::write_aligned`] do.
+    ///
+    /// The packet size is queried once when enforcement is enabled. Like the
+    /// stream size, the setting only applies to transfers through this handle.
+    pub fn set_alignment_enforcement(&self, pipe: Pipe, enforce: bool) -> Result<()> {
+        let packet_size = match enforce {
+            true => self.max_packet_size(pipe)? as u16,
+            false => 0,
+        };
+        let mut alignments = self.enforced_alignments.get();
+        alignments[pipe.slot()] = packet_size;
+        self.enforced_alignments.set(alignments);
+        Ok(())
+    }
+
+    /// Get the maximum packet size of the given pipe.
+    ///
+    /// The size only changes when the device re-enumerates, which requires
+    /// reopening it, so it is queried once and then cached. Unlike
+    /// [`Device::pipe_info`], this does not read the configuration descriptor.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the device does not expose the pipe.
+    fn max_packet_size(&self, pipe: Pipe) -> Result<usize> {
+        let mut packet_sizes = self.packet_sizes.get();
+        if packet_sizes[pipe.slot()] == 0 {
+            let info = self.query_pipe_info(&[])?;
+            let info = info.get(pipe).ok_or(D3xxError::InvalidParameter)?;
+            packet_sizes[pipe.slot()] = info.maximum_packet_size() as u16;
+            self.packet_sizes.set(packet_sizes);
         }
+        Ok(packet_sizes[pipe.slot()] as usize)
     }
 
-    /// Aborts all pending transfers for the given pipe.
-    pub fn abort_transfers(&self, pipe: Pipe) -> Result<()> {
-        unsafe { lib::FT_AbortPipe(self.handle, pipe as c_uchar) }
+    /// Check if alignment enforcement is enabled for the given pipe.
+    /// See [`Device::set_alignment_enforcement`].
+    pub fn alignment_enforced(&self, pipe: Pipe) -> bool {
+        self.enforced_alignments.get()[pipe.slot()] != 0
     }
 
-    /// Get the USB device descriptor.
-    pub fn device_descriptor(&self) -> Result<DeviceDescriptor> {
-        let mut device_descriptor = DeviceDescriptor::default();
-        unsafe {
-            lib::FT_GetDeviceDescriptor(self.handle, ptr_mut(&mut device_descriptor.inner))?;
+    /// Check the length of a transfer if alignment enforcement is enabled for the pipe.
+    pub(crate) fn check_enforced_alignment(&self, pipe: Pipe, len: usize) -> Result<()> {
+        match self.enforced_alignments.get()[pipe.slot()] {
+            0 => Ok(()),
+            packet_size => check_alignment(len, packet_size as usize),
         }
-        Ok(device_descriptor)
     }
 
-    /// Power cycles the device port. This causes the device to be re-enumermated by the host.
-    /// Consumes the object, meaning the device must be re-opened.
-    pub fn power_cycle_port(self) -> Result<()> {
-        // TODO: determine if device needs to be reopened.
-        unsafe { lib::FT_CycleDevicePort(self.handle) }
+    /// Reads a message from the notification pipe (endpoint `0x81`).
+    ///
+    /// When notification messages are enabled for an IN channel (see
+    /// [`OptionalFeatures`]), the chip reports data waiting on that channel
+    /// through this pipe instead of requiring it to be polled. The D3XX
+    /// documentation describes each notification as the number of bytes
+    /// pending and the endpoint address of the IN pipe holding them; the raw
+    /// message is returned as-is.
+    pub fn read_notification(&self, buf: &mut [u8]) -> Result<usize> {
+        self.shared_handle().read_notification(buf)
     }
-}
 
-impl Drop for Device {
-    fn drop(&mut self) {
+    /// Writes raw data to the session pipe (endpoint `0x01`).
+    ///
+    /// D3XX uses the session pipe internally to manage the chip, for example
+    /// when reading or writing its configuration. The command format is
+    /// proprietary and undocumented, so no command types are provided.
+    ///
+    /// The session pipe is OUT only; the chip replies through the notification
+    /// pipe (see [`Device::read_notification`]) or through control transfers.
+    ///
+    /// # Safety
+    /// Arbitrary session commands may reconfigure or lock up the chip, and
+    /// interfere with D3XX's own use of the session pipe, for example from a
+    /// concurrent [`Device::set_chip_config`].
+    pub unsafe fn session_write(&self, buf: &[u8]) -> Result<usize> {
+        let mut bytes_transferred = 0;
+        lib::FT_WritePipeEx(
+            self.handle,
+            ReservedPipe::Session.endpoint_address(),
+            buf.as_ptr(),
+            buf.len() as c_ulong,
+            &mut bytes_transferred,
+            std::ptr::null_mut(),
+        )?;
+        Ok(bytes_transferred as usize)
+    }
+
+    /// Configures the timeout for reading the notification pipe. See
+    /// [`Device::set_timeout`].
+    pub fn set_notification_timeout(&self, timeout: Duration) -> Result<()> {
         unsafe {
-            let _ = lib::FT_Close(self.handle);
+            lib::FT_SetPipeTimeout(
+                self.handle,
+                ReservedPipe::Notification.endpoint_address(),
+                timeout.as_millis().min(u32::MAX as u128) as c_ulong,
+            )
         }
     }
-}
 
-impl Debug for Device {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Device")
-            .field("handle", &self.handle)
-            .finish()
+    /// Writes data to the specified pipe, retrying transient failures
+    /// according to the given policy.
+    pub fn write_with_retry(&self, pipe: Pipe, buf: &[u8], policy: RetryPolicy) -> Result<usize> {
+        let (result, retries) = transfer::write_with_retry(self, pipe, buf, policy);
+        self.record_retries(retries);
+        result
     }
-}
 
-// =============================================================================
-/// Holds device information regarding a D3XX device attached to the system.
-#[derive(Clone, Debug, Default)]
-pub struct DeviceInfo {
-    /// Index in the D3XX device list. This value changes when the list is rebuilt!
-    index: usize,
-    inner: types::FT_DEVICE_LIST_INFO_NODE,
-}
+    /// Reads data from the specified pipe, retrying transient failures
+    /// according to the given policy.
+    pub fn read_with_retry(
+        &self,
+        pipe: Pipe,
+        buf: &mut [u8],
+        policy: RetryPolicy,
+    ) -> Result<usize> {
+        let (result, retries) = transfer::read_with_retry(self, pipe, buf, policy);
+        self.record_retries(retries);
+        result
+    }
 
-impl DeviceInfo {
-    /// Create a new DeviceInfo object from a raw value. The index is the index in the D3XX
-    /// device info list.
-    fn new(index: usize, node: types::FT_DEVICE_LIST_INFO_NODE) -> DeviceInfo {
-        DeviceInfo { index, inner: node }
+    /// Record the number of retries made for a transfer.
+    #[allow(unused_variables)]
+    fn record_retries(&self, retries: u64) {
+        #[cfg(feature = "stats")]
+        self.stats.record_retries(retries);
     }
 
-    /// Attempts to open the device represented by this struct.
-    pub fn open(&self) -> Result<Device> {
-        Device::open(&self)
+    /// Get the transfer statistics for this device.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &TransferStats {
+        &self.stats
     }
 
-    /// Gets the index of this device in the current D3XX device list.
-    pub fn index(&self) -> usize {
-        self.index
+    /// Reset the transfer statistics for this device to zero.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&self) {
+        self.stats.reset();
     }
 
-    /// Bit flags for USB3 or USB2 connection, etc.
-    pub fn flags(&self) -> u32 {
-        self.inner.Flags as _
+    /// Starts reading data from the specified pipe into `buf` without waiting
+    /// for the transfer to finish. See [`Transfer`].
+    ///
+    /// Only available on Windows.
+    #[cfg(windows)]
+    pub fn begin_read(&self, pipe: Pipe, buf: Vec<u8>) -> Result<Transfer<'_>> {
+        Transfer::begin_read(self, pipe, buf)
     }
 
-    /// Device type.
-    pub fn type_(&self) -> u32 {
-        self.inner.Type as _
+    /// Starts writing `buf` to the specified pipe without waiting for the
+    /// transfer to finish. See [`Transfer`].
+    ///
+    /// Only available on Windows.
+    #[cfg(windows)]
+    pub fn begin_write(&self, pipe: Pipe, buf: Vec<u8>) -> Result<Transfer<'_>> {
+        Transfer::begin_write(self, pipe, buf)
     }
 
-    /// Vendor ID.
-    pub fn vendor_id(&self) -> u16 {
-        ((self.inner.ID >> 16) & 0xFFFF) as _
+    /// Attempts to write data to the specified pipe without blocking.
+    ///
+    /// Returns `Ok(None)` if the device did not accept any data.
+    ///
+    /// The pipe timeout is temporarily lowered to the minimum of 1 ms (a
+    /// timeout of zero disables the timeout entirely) and afterwards restored
+    /// to the timeout last set through [`Device::set_timeout`], or the default
+    /// of 5 seconds. This works without querying the driver, which the Linux
+    /// D3XX library does not support. It is not atomic: other users of the device will see the lowered
+    /// timeout while the write is in progress, so external synchronization is
+    /// required if the device is shared.
+    pub fn try_write(&self, pipe: Pipe, buf: &[u8]) -> Result<Option<usize>> {
+        self.with_timeout(pipe, NON_BLOCKING_TIMEOUT, || match self.write(pipe, buf) {
+            Ok(0) | Err(D3xxError::Timeout) => Ok(None),
+            Ok(n) => Ok(Some(n)),
+            Err(e) => Err(e),
+        })
+    }
+
+    /// Attempts to read data from the specified pipe without blocking.
+    ///
+    /// Returns `Ok(None)` if no data is available.
+    ///
+    /// The same caveats regarding the pipe timeout as [`Device::try_write`] apply.
+    pub fn try_read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<Option<usize>> {
+        self.with_timeout(pipe, NON_BLOCKING_TIMEOUT, || match self.read(pipe, buf) {
+            Ok(0) | Err(D3xxError::Timeout) => Ok(None),
+            Ok(n) => Ok(Some(n)),
+            Err(e) => Err(e),
+        })
     }
 
-    /// Product ID.
-    pub fn product_id(&self) -> u16 {
-        (self.inner.ID & 0xFFFF) as _
+    /// Writes data to the specified pipe using a different timeout for this
+    /// transfer only.
+    ///
+    /// The pipe timeout is changed for the duration of the write and restored
+    /// afterwards, even if the write fails. The same caveats regarding shared
+    /// devices as [`Device::try_write`] apply.
+    pub fn write_with_timeout(&self, pipe: Pipe, buf: &[u8], timeout: Duration) -> Result<usize> {
+        self.with_timeout(pipe, timeout, || self.write(pipe, buf))
     }
 
-    /// Location identifier.
+    /// Reads data from the specified pipe using a different timeout for this
+    /// transfer only.
+    ///
+    /// See [`Device::write_with_timeout`].
+    pub fn read_with_timeout(
+        &self,
+        pipe: Pipe,
+        buf: &mut [u8],
+        timeout: Duration,
+    ) -> Result<usize> {
+        self.with_timeout(pipe, timeout, || self.read(pipe, buf))
+    }
+
+    /// Runs `f` with the timeout of the given pipe temporarily changed.
+    /// The timeout last set through this handle is restored even if `f` fails.
+    fn with_timeout<T>(
+        &self,
+        pipe: Pipe,
+        timeout: Duration,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let original = self.tracked_timeout(pipe);
+        self.set_timeout(pipe, timeout)?;
+        let result = f();
+        let restored = self.set_timeout(pipe, original);
+        // An error from the transfer itself is more useful than one from restoring.
+        let value = result?;
+        restored?;
+        Ok(value)
+    }
+
+    /// Writes the entire buffer to the specified pipe, issuing as many
+    /// transfers as needed.
+    ///
+    /// # Errors
+    /// Any error from [`Device::write`] is returned immediately. A transfer
+    /// which completes without writing anything results in [`D3xxError::Timeout`].
+    pub fn write_all(&self, pipe: Pipe, buf: &[u8]) -> Result<()> {
+        transfer::write_all(self, pipe, buf)
+    }
+
+    /// Reads from the specified pipe until the buffer is completely filled,
+    /// issuing as many transfers as needed.
+    ///
+    /// # Errors
+    /// Any error from [`Device::read`] is returned immediately. A transfer
+    /// which completes without reading anything results in [`D3xxError::Timeout`].
+    pub fn read_exact(&self, pipe: Pipe, buf: &mut [u8]) -> Result<()> {
+        transfer::read_exact(self, pipe, buf)
+    }
+
+    /// Reads a little-endian `u32` from the specified pipe. See [`Device::read_exact`].
+    pub fn read_u32_le(&self, pipe: Pipe) -> Result<u32> {
+        let mut buf = [0; 4];
+        self.read_exact(pipe, &mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    /// Reads a big-endian `u32` from the specified pipe. See [`Device::read_exact`].
+    pub fn read_u32_be(&self, pipe: Pipe) -> Result<u32> {
+        let mut buf = [0; 4];
+        self.read_exact(pipe, &mut buf)?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    /// Reads a little-endian `u64` from the specified pipe. See [`Device::read_exact`].
+    pub fn read_u64_le(&self, pipe: Pipe) -> Result<u64> {
+        let mut buf = [0; 8];
+        self.read_exact(pipe, &mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// Reads a big-endian `u64` from the specified pipe. See [`Device::read_exact`].
+    pub fn read_u64_be(&self, pipe: Pipe) -> Result<u64> {
+        let mut buf = [0; 8];
+        self.read_exact(pipe, &mut buf)?;
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    /// Writes a `u32` to the specified pipe in little-endian order. See [`Device::write_all`].
+    pub fn write_u32_le(&self, pipe: Pipe, value: u32) -> Result<()> {
+        self.write_all(pipe, &value.to_le_bytes())
+    }
+
+    /// Writes a `u32` to the specified pipe in big-endian order. See [`Device::write_all`].
+    pub fn write_u32_be(&self, pipe: Pipe, value: u32) -> Result<()> {
+        self.write_all(pipe, &value.to_be_bytes())
+    }
+
+    /// Writes a `u64` to the specified pipe in little-endian order. See [`Device::write_all`].
+    pub fn write_u64_le(&self, pipe: Pipe, value: u64) -> Result<()> {
+        self.write_all(pipe, &value.to_le_bytes())
+    }
+
+    /// Writes a `u64` to the specified pipe in big-endian order. See [`Device::write_all`].
+    pub fn write_u64_be(&self, pipe: Pipe, value: u64) -> Result<()> {
+        self.write_all(pipe, &value.to_be_bytes())
+    }
+
+    /// Reads a value of any plain-old-data type from the specified pipe, in
+    /// native byte order. See [`Device::read_exact`].
+    #[cfg(feature = "bytemuck")]
+    pub fn read_typed<T: bytemuck::Pod>(&self, pipe: Pipe) -> Result<T> {
+        let mut value = T::zeroed();
+        self.read_exact(pipe, bytemuck::bytes_of_mut(&mut value))?;
+        Ok(value)
+    }
+
+    /// Reads `len` bytes from the specified pipe into `writer`, at most
+    /// `chunk_size` bytes at a time, returning the number of bytes transferred.
+    ///
+    /// Each chunk is written to `writer` as soon as it is read, so only one
+    /// chunk is held in memory, making this suitable for recording large
+    /// amounts of data straight to a file.
+    ///
+    /// # Errors
+    /// - [`ReadWriteError::Read`] with [`D3xxError::InvalidParameter`] if
+    ///   `chunk_size` is zero, with [`D3xxError::Timeout`] if a transfer
+    ///   completes without reading anything, or with any error from [`Device::read`].
+    /// - [`ReadWriteError::Write`] with any error from `writer`.
+    pub fn read_into_writer<W: std::io::Write>(
+        &self,
+        pipe: Pipe,
+        writer: &mut W,
+        len: usize,
+        chunk_size: usize,
+    ) -> Result<u64, ReadWriteError> {
+        if chunk_size == 0 {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let mut chunk = vec![0; chunk_size.min(len)];
+        let mut total = 0;
+        while total < len {
+            let want = chunk.len().min(len - total);
+            match self.read(pipe, &mut chunk[..want])? {
+                0 => Err(D3xxError::Timeout)?,
+                n => {
+                    writer.write_all(&chunk[..n])?;
+                    total += n;
+                }
+            }
+        }
+        Ok(total as u64)
+    }
+
+    /// Writes the given buffers to the specified pipe as a single transfer,
+    /// returning the total number of bytes written.
+    ///
+    /// D3XX has no scatter-gather support, so the buffers are first copied into
+    /// one heap allocation. Like [`Device::write`], the combined length is
+    /// limited to what a single transfer can hold.
+    pub fn write_vectored(&self, pipe: Pipe, bufs: &[&[u8]]) -> Result<usize> {
+        self.write(pipe, &bufs.concat())
+    }
+
+    /// Reads from the specified pipe as a single transfer, filling the given
+    /// buffers in order and returning the total number of bytes read.
+    ///
+    /// The transfer is read into one heap allocation the size of all buffers
+    /// combined and then copied out, as D3XX has no scatter-gather support.
+    pub fn read_into_vectored(&self, pipe: Pipe, bufs: &mut [&mut [u8]]) -> Result<usize> {
+        let mut data = vec![0; bufs.iter().map(|buf| buf.len()).sum()];
+        let n = self.read(pipe, &mut data)?;
+        let mut remaining = &data[..n];
+        for buf in bufs.iter_mut() {
+            let len = buf.len().min(remaining.len());
+            buf[..len].copy_from_slice(&remaining[..len]);
+            remaining = &remaining[len..];
+        }
+        Ok(n)
+    }
+
+    /// Writes the payload followed by its CRC-32 as a single transfer,
+    /// returning the number of bytes written including the checksum.
+    ///
+    /// The checksum is the standard CRC-32/IEEE (polynomial `0x04C11DB7`,
+    /// reflected, initial value and final XOR `0xFFFFFFFF`) of the payload,
+    /// appended in little-endian byte order. The gateware should compute it over
+    /// the payload as it arrives and compare it against the last four bytes.
+    #[cfg(feature = "crc")]
+    pub fn write_with_crc(&self, pipe: Pipe, payload: &[u8]) -> Result<usize> {
+        let mut data = Vec::with_capacity(payload.len() + 4);
+        data.extend_from_slice(payload);
+        data.extend_from_slice(&crc32fast::hash(payload).to_le_bytes());
+        self.write(pipe, &data)
+    }
+
+    /// Reads a payload followed by its CRC-32 as a single transfer, copying the
+    /// payload into `buf` and returning its length.
+    ///
+    /// The transfer may be up to four bytes longer than `buf` to hold the
+    /// checksum, which is expected in the format described in
+    /// [`Device::write_with_crc`].
+    ///
+    /// # Errors
+    /// - [`D3xxError::InvalidFrame`] if the transfer is too short to hold a
+    ///   checksum or the checksum does not match.
+    /// - Any error from [`Device::read`].
+    #[cfg(feature = "crc")]
+    pub fn read_with_crc_verify(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        let mut data = vec![0; buf.len() + 4];
+        let n = self.read(pipe, &mut data)?;
+        let Some(len) = n.checked_sub(4) else {
+            return Err(D3xxError::InvalidFrame("transfer shorter than crc"));
+        };
+        let (payload, crc) = data[..n].split_at(len);
+        if crc32fast::hash(payload).to_le_bytes() != crc {
+            Err(D3xxError::InvalidFrame("crc mismatch"))?;
+        }
+        buf[..len].copy_from_slice(payload);
+        Ok(len)
+    }
+
+    /// Measures write throughput by writing as much data as possible to the
+    /// specified pipe for the given duration.
+    ///
+    /// Timeouts are not treated as errors, as they only lower the throughput.
+    pub fn measure_write_bandwidth(
+        &self,
+        pipe: Pipe,
+        duration: Duration,
+    ) -> Result<BandwidthReport> {
+        let buf = vec![0u8; BandwidthReport::TRANSFER_SIZE];
+        BandwidthReport::measure(duration, || self.write(pipe, &buf))
+    }
+
+    /// Measures read throughput by reading as much data as possible from the
+    /// specified pipe for the given duration.
+    ///
+    /// Timeouts are not treated as errors, as they only lower the throughput.
+    pub fn measure_read_bandwidth(
+        &self,
+        pipe: Pipe,
+        duration: Duration,
+    ) -> Result<BandwidthReport> {
+        let mut buf = vec![0u8; BandwidthReport::TRANSFER_SIZE];
+        BandwidthReport::measure(duration, || self.read(pipe, &mut buf))
+    }
+
+    /// Writes data to the OUT pipe of the given pair. See [`Device::write`].
+    pub fn write_pipe_pair(&self, pair: &PipePair, data: &[u8]) -> Result<usize> {
+        self.write(pair.output, data)
+    }
+
+    /// Reads data from the IN pipe of the given pair. See [`Device::read`].
+    pub fn read_pipe_pair(&self, pair: &PipePair, buf: &mut [u8]) -> Result<usize> {
+        self.read(pair.input, buf)
+    }
+
+    /// Creates a [`PipeReader`] implementing [`std::io::Read`] over the given IN pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an OUT pipe.
+    pub fn pipe_reader(&self, pipe: Pipe) -> Result<PipeReader<'_>> {
+        PipeReader::new(self, pipe)
+    }
+
+    /// Creates a [`PipeWriter`] implementing [`std::io::Write`] over the given OUT pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an IN pipe.
+    pub fn pipe_writer(&self, pipe: Pipe) -> Result<PipeWriter<'_>> {
+        PipeWriter::new(self, pipe)
+    }
+
+    /// Creates a [`RateLimitedWriter`] over the given OUT pipe, limiting
+    /// throughput to `max_bps` bytes per second.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an IN pipe or
+    /// `max_bps` is zero.
+    pub fn rate_limited_writer(&self, pipe: Pipe, max_bps: u64) -> Result<RateLimitedWriter<'_>> {
+        RateLimitedWriter::new(self, pipe, max_bps)
+    }
+
+    /// Creates a [`FramedReader`] yielding frames of `frame_size` bytes from the given IN pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an OUT pipe, or if
+    /// `frame_size` is not a non-zero multiple of the pipe's maximum packet size.
+    pub fn framed_reader(&self, pipe: Pipe, frame_size: usize) -> Result<FramedReader<'_>> {
+        FramedReader::new(self, pipe, frame_size)
+    }
+
+    /// Creates a [`ChunkedPipeReader`] yielding chunks of up to `chunk_size`
+    /// bytes from the given IN pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an OUT pipe, or if
+    /// `chunk_size` is not a non-zero multiple of 512 bytes.
+    pub fn read_chunks(&self, pipe: Pipe, chunk_size: usize) -> Result<ChunkedPipeReader<'_>> {
+        ChunkedPipeReader::new(self, pipe, chunk_size)
+    }
+
+    /// Creates a [`FramedWriter`] writing whole frames to the given OUT pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an IN pipe.
+    pub fn framed_writer(&self, pipe: Pipe) -> Result<FramedWriter<'_>> {
+        FramedWriter::new(self, pipe)
+    }
+
+    /// Get the number of bytes queued in the driver for the specified pipe.
+    ///
+    /// For an IN pipe, this is the amount of data received and waiting to be
+    /// read. For an OUT pipe, it is the amount of data not yet sent to the device.
+    ///
+    /// Only the Linux D3XX library supports querying the queue status.
+    /// Elsewhere this returns [`D3xxError::LibraryAccessFailed`].
+    pub fn queue_status(&self, pipe: Pipe) -> Result<u32> {
+        let mut amount: c_ulong = 0;
+        unsafe {
+            match pipe.is_read_pipe() {
+                true => lib::FT_GetReadQueueStatus(self.handle, pipe as c_uchar, &mut amount),
+                false => lib::FT_GetWriteQueueStatus(self.handle, pipe as c_uchar, &mut amount),
+            }?;
+        }
+        Ok(amount as u32)
+    }
+
+    /// Check if any data is queued for the specified pipe. See [`Device::queue_status`].
+    pub fn data_available(&self, pipe: Pipe) -> Result<bool> {
+        Ok(self.queue_status(pipe)? > 0)
+    }
+
+    /// Discards any data cached in an IN pipe.
+    /// If `pipe` is an OUT pipe, an `InvalidParameter` error is returned.
+    ///
+    /// Aborting a transfer with [`Device::abort_transfers`] does not guarantee
+    /// that the host-side buffer is empty, so this should be called afterwards
+    /// to get rid of any stale data.
+    pub fn flush_pipe(&self, pipe: Pipe) -> Result<()> {
+        if !pipe.is_read_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        unsafe { lib::FT_FlushPipe(self.handle, pipe as c_uchar) }
+    }
+
+    /// Discards any data cached in all IN pipes exposed by the device.
+    /// See [`Device::available_pipes`].
+    pub fn flush_all_read_pipes(&self) -> Result<()> {
+        for pipe in self.available_pipes()? {
+            if pipe.is_read_pipe() {
+                self.flush_pipe(pipe)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Discards any data cached in every IN pipe exposed by the device.
+    /// See [`Device::flush_pipe`].
+    ///
+    /// All pipes are attempted even if one fails. OUT pipes hold no data to
+    /// discard and are left alone, as are pipes the device does not expose.
+    ///
+    /// # Errors
+    /// Returns the first error other than [`D3xxError::NotSupported`].
+    pub fn flush_all_pipes(&self) -> Result<()> {
+        let mut first_error = None;
+        for pipe in self.available_pipes()? {
+            if !pipe.is_read_pipe() {
+                continue;
+            }
+            match self.flush_pipe(pipe) {
+                Ok(()) | Err(D3xxError::NotSupported) => (),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Reads and discards data from an IN pipe until a read times out,
+    /// returning the number of bytes discarded.
+    ///
+    /// The final read always waits for the full pipe timeout, so consider
+    /// lowering it with [`Device::set_timeout`] first.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is an OUT pipe or
+    /// `buf_size` is zero.
+    pub fn drain_pipe(&self, pipe: Pipe, buf_size: usize) -> Result<u64> {
+        if !pipe.is_read_pipe() || buf_size == 0 {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let mut buf = vec![0; buf_size];
+        let mut discarded = 0;
+        loop {
+            match self.read(pipe, &mut buf) {
+                Ok(0) | Err(D3xxError::Timeout) => return Ok(discarded),
+                Ok(n) => discarded += n as u64,
+                Err(e) => Err(e)?,
+            }
+        }
+    }
+
+    /// Configures a timeout for the specified endpoint. Reading and writing will
+    /// timeout in the event the operation hangs for the given duration.
+    ///
+    /// The new value is only valid as long as the device is open; re-opening the device
+    /// will reset the timeout to the default of 5 seconds.
+    pub fn set_timeout(&self, pipe: Pipe, timeout: Duration) -> Result<()> {
+        let millis = timeout.as_millis().min(u32::MAX as u128) as u32;
+        unsafe {
+            lib::FT_SetPipeTimeout(self.handle, pipe as c_uchar, millis as c_ulong)?;
+        }
+        let mut timeouts = self.timeouts.get();
+        timeouts[pipe.slot()] = millis;
+        self.timeouts.set(timeouts);
+        Ok(())
+    }
+
+    /// Configures the same timeout for every pipe exposed by the device.
+    /// See [`Device::set_timeout`] and [`Device::available_pipes`].
+    pub fn set_timeout_all(&self, timeout: Duration) -> Result<()> {
+        for pipe in self.available_pipes()? {
+            self.set_timeout(pipe, timeout)?;
+        }
+        Ok(())
+    }
+
+    /// Configures the same timeout for both pipes of the given pair. See [`Device::set_timeout`].
+    pub fn set_pair_timeout(&self, pair: &PipePair, timeout: Duration) -> Result<()> {
+        self.set_timeout(pair.input, timeout)?;
+        self.set_timeout(pair.output, timeout)
+    }
+
+    /// Get the timeout configured for the specified pipe.
+    ///
+    /// Where the D3XX library cannot report the timeout, as on Linux, the
+    /// timeout last set through this handle is returned instead.
+    pub fn get_timeout(&self, pipe: Pipe) -> Result<Duration> {
+        let mut timeout_millis: c_ulong = 0;
+        let result = unsafe {
+            lib::FT_GetPipeTimeout(self.handle, pipe as c_uchar, ptr_mut(&mut timeout_millis))
+        };
+        match result {
+            Ok(()) => Ok(Duration::from_millis(timeout_millis as u64)),
+            Err(D3xxError::LibraryAccessFailed(_)) => Ok(self.tracked_timeout(pipe)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the timeout last set on the specified pipe through this handle, or
+    /// the default of 5 seconds.
+    fn tracked_timeout(&self, pipe: Pipe) -> Duration {
+        Duration::from_millis(self.timeouts.get()[pipe.slot()] as u64)
+    }
+
+    /// Set the driver-level USB transfer sizes for IN and OUT transfers, in bytes.
+    ///
+    /// Larger transfer sizes allow the driver to pipeline more data and can
+    /// significantly improve throughput. Use multiples of 32 KiB for the FT601
+    /// and multiples of 4 KiB for the FT600.
+    ///
+    /// D3XX provides no way to read the sizes back. Only the Windows D3XX
+    /// library supports this; elsewhere it returns [`D3xxError::LibraryAccessFailed`].
+    pub fn set_usb_transfer_sizes(&self, in_size: u32, out_size: u32) -> Result<()> {
+        unsafe { lib::FT_SetUSBParameters(self.handle, in_size as c_ulong, out_size as c_ulong) }
+    }
+
+    /// Set the USB latency timer for the specified pipe, in milliseconds.
+    ///
+    /// The latency timer controls how long the device waits before notifying
+    /// the host of a partially filled buffer. Lower values reduce latency for
+    /// small transfers, while higher values (e.g. 16 ms) favour throughput.
+    /// Valid values are typically 2-255 ms; the behavior of 0 and 1 depends on
+    /// the device. This is unrelated to the pipe timeout set with [`Device::set_timeout`].
+    ///
+    /// Only the Windows D3XX library supports the latency timer. Elsewhere this
+    /// returns [`D3xxError::LibraryAccessFailed`].
+    pub fn set_latency_timer(&self, pipe: Pipe, timer_ms: u8) -> Result<()> {
+        unsafe { lib::FT_SetLatencyTimer(self.handle, pipe as c_uchar, timer_ms) }
+    }
+
+    /// Get the USB latency timer for the specified pipe, in milliseconds.
+    ///
+    /// See [`Device::set_latency_timer`].
+    pub fn get_latency_timer(&self, pipe: Pipe) -> Result<u8> {
+        let mut timer: c_uchar = 0;
+        unsafe {
+            lib::FT_GetLatencyTimer(self.handle, pipe as c_uchar, &mut timer)?;
+        }
+        Ok(timer)
+    }
+
+    /// Set how long the device must be idle before the driver selectively
+    /// suspends its USB link. A timeout of zero disables selective suspend.
+    ///
+    /// The timeout has one-second resolution. It is independent of the pipe
+    /// timeout set with [`Device::set_timeout`]: a pending transfer keeps the
+    /// device active, so the link is only suspended once no transfer has been
+    /// in progress for the full suspend timeout. The driver wakes the link
+    /// again when the next transfer starts.
+    ///
+    /// Only the Windows D3XX library supports selective suspend, and it
+    /// provides no call to suspend the device explicitly. Elsewhere this
+    /// returns [`D3xxError::LibraryAccessFailed`].
+    pub fn set_suspend_timeout(&self, timeout: Duration) -> Result<()> {
+        unsafe { lib::FT_SetSuspendTimeout(self.handle, timeout.as_secs() as c_ulong) }
+    }
+
+    /// Get how long the device must be idle before its USB link is suspended.
+    ///
+    /// See [`Device::set_suspend_timeout`].
+    pub fn get_suspend_timeout(&self) -> Result<Duration> {
+        let mut timeout: c_ulong = 0;
+        unsafe {
+            lib::FT_GetSuspendTimeout(self.handle, &mut timeout)?;
+        }
+        Ok(Duration::from_secs(timeout as u64))
+    }
+
+    /// Sets streaming protocol transfer for the specified pipe. This is for
+    /// applications that read or write a fixed size of data to or from the device.
+    pub fn set_stream_size(&self, pipe: Pipe, stream_size: Option<u32>) -> Result<()> {
+        unsafe {
+            match stream_size {
+                Some(size) => lib::FT_SetStreamPipe(
+                    self.handle,
+                    false as c_uchar,
+                    false as c_uchar,
+                    pipe as c_uchar,
+                    size as c_ulong,
+                ),
+                None => lib::FT_ClearStreamPipe(
+                    self.handle,
+                    false as c_uchar,
+                    false as c_uchar,
+                    pipe as c_uchar,
+                ),
+            }?;
+        }
+        self.update_stream_sizes(std::iter::once(pipe), stream_size);
+        Ok(())
+    }
+
+    /// Sets the same stream size on every IN pipe. See [`Device::set_stream_size`].
+    ///
+    /// This is a single D3XX call with its "all read pipes" flag set, rather
+    /// than one call per pipe.
+    pub fn set_stream_size_all_read(&self, stream_size: u32) -> Result<()> {
+        unsafe {
+            lib::FT_SetStreamPipe(
+                self.handle,
+                false as c_uchar,
+                true as c_uchar,
+                0,
+                stream_size as c_ulong,
+            )?;
+        }
+        self.update_stream_sizes(Pipe::read_pipes(), Some(stream_size));
+        Ok(())
+    }
+
+    /// Sets the same stream size on every OUT pipe. See [`Device::set_stream_size`].
+    ///
+    /// This is a single D3XX call with its "all write pipes" flag set, rather
+    /// than one call per pipe.
+    pub fn set_stream_size_all_write(&self, stream_size: u32) -> Result<()> {
+        unsafe {
+            lib::FT_SetStreamPipe(
+                self.handle,
+                true as c_uchar,
+                false as c_uchar,
+                0,
+                stream_size as c_ulong,
+            )?;
+        }
+        self.update_stream_sizes(Pipe::write_pipes(), Some(stream_size));
+        Ok(())
+    }
+
+    /// Clears the stream size of every pipe. See [`Device::set_stream_size`].
+    ///
+    /// This is a single D3XX call with both its "all read pipes" and "all
+    /// write pipes" flags set, rather than one call per pipe.
+    pub fn clear_stream_size_all(&self) -> Result<()> {
+        unsafe {
+            lib::FT_ClearStreamPipe(self.handle, true as c_uchar, true as c_uchar, 0)?;
+        }
+        self.update_stream_sizes(Pipe::all(), None);
+        Ok(())
+    }
+
+    /// Record the stream size set on the given pipes.
+    fn update_stream_sizes(&self, pipes: impl Iterator<Item = Pipe>, stream_size: Option<u32>) {
+        let mut stream_sizes = self.stream_sizes.get();
+        for pipe in pipes {
+            stream_sizes[pipe.slot()] = stream_size;
+        }
+        self.stream_sizes.set(stream_sizes);
+    }
+
+    /// Get the stream size last set on the specified pipe through this handle.
+    ///
+    /// D3XX cannot report the stream size, so changes made elsewhere are not reflected.
+    pub fn stream_size(&self, pipe: Pipe) -> Option<u32> {
+        self.stream_sizes.get()[pipe.slot()]
+    }
+
+    /// Apply all settings in the given configuration to the specified pipe.
+    ///
+    /// The timeout is applied first, then the stream size, then the latency
+    /// timer if one is given.
+    pub fn apply_pipe_config(&self, pipe: Pipe, config: &PipeConfig) -> Result<()> {
+        self.set_timeout(pipe, config.timeout)?;
+        self.set_stream_size(pipe, config.stream_size)?;
+        if let Some(latency_ms) = config.latency_ms {
+            self.set_latency_timer(pipe, latency_ms)?;
+        }
+        Ok(())
+    }
+
+    /// Read the current configuration of the specified pipe.
+    ///
+    /// The timeout and stream size are the ones last set through this handle,
+    /// since the Linux D3XX library can report neither; changes made through
+    /// other handles are not reflected. The latency timer is `None` where the
+    /// D3XX library does not support it.
+    pub fn snapshot_pipe_config(&self, pipe: Pipe) -> Result<PipeConfig> {
+        let latency_ms = match self.get_latency_timer(pipe) {
+            Ok(latency_ms) => Some(latency_ms),
+            Err(D3xxError::LibraryAccessFailed(_)) => None,
+            Err(e) => Err(e)?,
+        };
+        Ok(PipeConfig {
+            timeout: self.tracked_timeout(pipe),
+            stream_size: self.stream_size(pipe),
+            latency_ms,
+        })
+    }
+
+    /// Restore a configuration previously obtained from [`Device::snapshot_pipe_config`].
+    ///
+    /// Equivalent to [`Device::apply_pipe_config`].
+    pub fn restore_pipe_config(&self, pipe: Pipe, config: &PipeConfig) -> Result<()> {
+        self.apply_pipe_config(pipe, config)
+    }
+
+    /// Aborts all pending transfers for the given pipe.
+    pub fn abort_transfers(&self, pipe: Pipe) -> Result<()> {
+        self.shared_handle().abort(pipe)
+    }
+
+    /// Get a copy of the handle for reading or aborting pipes from other
+    /// threads. See [`SharedHandle`].
+    pub(crate) fn shared_handle(&self) -> SharedHandle {
+        SharedHandle(self.handle)
+    }
+
+    /// Aborts all pending transfers for every pipe exposed by the device.
+    /// See [`Device::available_pipes`].
+    pub fn abort_all_transfers(&self) -> Result<()> {
+        for pipe in self.available_pipes()? {
+            self.abort_transfers(pipe)?;
+        }
+        Ok(())
+    }
+
+    /// Get the USB device descriptor.
+    pub fn device_descriptor(&self) -> Result<DeviceDescriptor> {
+        let mut device_descriptor = DeviceDescriptor::default();
+        unsafe {
+            lib::FT_GetDeviceDescriptor(self.handle, ptr_mut(&mut device_descriptor.inner))?;
+        }
+        Ok(device_descriptor)
+    }
+
+    /// Get the USB configuration descriptor.
+    pub fn configuration_descriptor(&self) -> Result<ConfigurationDescriptor> {
+        let mut configuration_descriptor = ConfigurationDescriptor {
+            super_speed: self.is_usb3()?,
+            ..Default::default()
+        };
+        unsafe {
+            lib::FT_GetConfigurationDescriptor(
+                self.handle,
+                ptr_mut(&mut configuration_descriptor.inner),
+            )?;
+        }
+        Ok(configuration_descriptor)
+    }
+
+    /// Get the USB interface descriptor at the given index.
+    ///
+    /// Valid indices range up to [`ConfigurationDescriptor::num_interfaces`].
+    pub fn interface_descriptor(&self, interface_index: u8) -> Result<InterfaceDescriptor> {
+        let mut interface_descriptor = InterfaceDescriptor::default();
+        unsafe {
+            lib::FT_GetInterfaceDescriptor(
+                self.handle,
+                interface_index,
+                ptr_mut(&mut interface_descriptor.inner),
+            )?;
+        }
+        Ok(interface_descriptor)
+    }
+
+    /// Read a raw USB descriptor of the given type and index.
+    fn raw_descriptor(&self, descriptor_type: u8, index: u8, buf: &mut [u8]) -> Result<usize> {
+        let mut bytes_transferred: c_ulong = 0;
+        unsafe {
+            lib::FT_GetDescriptor(
+                self.handle,
+                descriptor_type,
+                index,
+                buf.as_mut_ptr(),
+                buf.len() as c_ulong,
+                ptr_mut(&mut bytes_transferred),
+            )?;
+        }
+        Ok(bytes_transferred as usize)
+    }
+
+    /// Get the Binary Object Store (BOS) descriptor, which lists the device's
+    /// capabilities such as SuperSpeed support.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidFrame`] if the descriptor is malformed.
+    pub fn bos_descriptor(&self) -> Result<BosDescriptor> {
+        const TYPE: u8 = constants::FT_BOS_DESCRIPTOR_TYPE;
+
+        let mut header = [0u8; 5];
+        self.raw_descriptor(TYPE, 0, &mut header)?;
+        let mut buf = vec![0u8; u16::from_le_bytes([header[2], header[3]]) as usize];
+        let n = self.raw_descriptor(TYPE, 0, &mut buf)?;
+        BosDescriptor::from_bytes(&buf[..n])
+    }
+
+    /// Read the chip configuration from the device.
+    pub fn chip_config(&self) -> Result<ChipConfiguration> {
+        let mut config = ChipConfiguration::default();
+        unsafe {
+            lib::FT_GetChipConfiguration(self.handle, ptr_mut(&mut config.inner))?;
+        }
+        Ok(config)
+    }
+
+    /// Write a new chip configuration to the device.
+    ///
+    /// The device re-enumerates once the configuration is written, so it must be re-opened.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidOptions`] without writing anything if the
+    /// configuration fails [`ChipConfiguration::validate`].
+    pub fn set_chip_config(&self, config: &ChipConfiguration) -> Result<()> {
+        config.validate()?;
+        let mut inner = config.inner;
+        unsafe { lib::FT_SetChipConfiguration(self.handle, ptr_mut(&mut inner)) }
+    }
+
+    /// Performs a USB control transfer on the default control endpoint.
+    ///
+    /// `request_type` is the `bmRequestType` field of the setup packet:
+    /// - bit 7 is the direction; `0x80` transfers from the device to the host
+    ///   (`buf` is filled), otherwise `buf` is sent to the device.
+    /// - bits 6..5 are the type; `0x00` for standard, `0x20` for class,
+    ///   and `0x40` for vendor requests.
+    /// - bits 4..0 are the recipient; `0x00` for the device, `0x01` for an
+    ///   interface, and `0x02` for an endpoint.
+    ///
+    /// Vendor commands to the device therefore use `0x40` (host to device)
+    /// or `0xC0` (device to host).
+    ///
+    /// Returns the number of bytes transferred.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `buf` is longer than 65535 bytes.
+    pub fn control_transfer(
+        &self,
+        request_type: u8,
+        request: u8,
+        value: u16,
+        index: u16,
+        buf: &mut [u8],
+    ) -> Result<usize> {
+        let setup_packet = types::FT_SETUP_PACKET {
+            RequestType: request_type,
+            Request: request,
+            Value: value,
+            Index: index,
+            Length: buf.len().try_into().or(Err(D3xxError::InvalidParameter))?,
+        };
+        let mut bytes_transferred: c_ulong = 0;
+        unsafe {
+            lib::FT_ControlTransfer(
+                self.handle,
+                setup_packet,
+                buf.as_mut_ptr(),
+                buf.len() as c_ulong,
+                ptr_mut(&mut bytes_transferred),
+            )?;
+        }
+        Ok(bytes_transferred as usize)
+    }
+
+    /// Read a USB string descriptor in the given language.
+    ///
+    /// Index 0 holds the list of supported language IDs rather than a string.
+    pub fn raw_string_descriptor(&self, index: u8, language_id: u16) -> Result<StringDescriptor> {
+        // FT_GetDescriptor cannot select the language, so this is requested
+        // directly.
+        const GET_DESCRIPTOR: u8 = 0x06;
+
+        let mut buf = [0u8; 255];
+        let n = self.control_transfer(
+            0x80,
+            GET_DESCRIPTOR,
+            (constants::FT_STRING_DESCRIPTOR_TYPE as u16) << 8 | index as u16,
+            language_id,
+            &mut buf,
+        )?;
+        StringDescriptor::from_bytes(&buf[..n])
+    }
+
+    /// Get the language IDs supported by the device's string descriptors.
+    ///
+    /// These are read from string descriptor 0. See [`LanguageId`] for the
+    /// corresponding locales.
+    pub fn supported_language_ids(&self) -> Result<Vec<u16>> {
+        Ok(self.raw_string_descriptor(0, 0)?.as_utf16().to_vec())
+    }
+
+    /// Read a USB string descriptor in the given language and decode it.
+    pub fn string_descriptor(&self, index: u8, language_id: u16) -> Result<String> {
+        Ok(self.raw_string_descriptor(index, language_id)?.to_string())
+    }
+
+    /// Read the manufacturer string in English.
+    ///
+    /// Returns an empty string if the device does not provide one.
+    pub fn manufacturer_string(&self) -> Result<String> {
+        self.english_string(self.device_descriptor()?.manufacturer_string_index())
+    }
+
+    /// Read the product string in English.
+    ///
+    /// Returns an empty string if the device does not provide one.
+    pub fn product_string(&self) -> Result<String> {
+        self.english_string(self.device_descriptor()?.product_string_index())
+    }
+
+    /// Read the serial number string in English.
+    ///
+    /// Returns an empty string if the device does not provide one.
+    pub fn serial_string(&self) -> Result<String> {
+        self.english_string(self.device_descriptor()?.serial_number_string_index())
+    }
+
+    /// Read a string descriptor in English, treating index 0 as no string.
+    fn english_string(&self, index: u8) -> Result<String> {
+        match index {
+            0 => Ok(String::new()),
+            index => self.string_descriptor(index, LANGUAGE_ID_ENGLISH_US),
+        }
+    }
+
+    /// Resets the device port without re-enumerating the device.
+    ///
+    /// Pending transfers are discarded, but the handle stays valid, so the device
+    /// can continue to be used. Use [`Device::power_cycle_port`] for a full
+    /// re-enumeration, which invalidates the handle.
+    pub fn reset_port(&self) -> Result<()> {
+        unsafe { lib::FT_ResetDevicePort(self.handle) }
+    }
+
+    /// Aborts pending transfers on all pipes, then resets the device port.
+    /// See [`Device::reset_port`].
+    pub fn abort_and_reset(&self) -> Result<()> {
+        self.abort_all_transfers()?;
+        self.reset_port()
+    }
+
+    /// Recovers from a desynchronized protocol state by performing the steps
+    /// selected in `options`, in this order:
+    ///
+    /// 1. [`Device::abort_transfers`] on every pipe ([`ResetOptions::ABORT_TRANSFERS`]).
+    /// 2. [`Device::flush_pipe`] on every IN pipe ([`ResetOptions::FLUSH_PIPES`]).
+    /// 3. [`Device::set_stream_size`] with `None` on every pipe
+    ///    ([`ResetOptions::CLEAR_STREAMS`]).
+    /// 4. [`Device::reset_port`] ([`ResetOptions::RESET_PORT`]).
+    ///
+    /// The first three steps only touch the pipes the device exposes, see
+    /// [`Device::available_pipes`].
+    ///
+    /// The first three steps only affect the host side and can be done while
+    /// the device stays online. Resetting the port also clears state held by
+    /// the chip, and the handle stays valid. Neither reloads the chip
+    /// configuration; that requires [`Device::power_cycle_port`].
+    ///
+    /// Every selected step is attempted even if an earlier one fails.
+    ///
+    /// # Errors
+    /// The first error encountered, including failing to list the pipes.
+    pub fn drain_and_reset(&self, options: ResetOptions) -> Result<()> {
+        let mut result = Ok(());
+        let mut record = |r: Result<()>| {
+            if result.is_ok() {
+                result = r;
+            }
+        };
+        let pipe_steps =
+            ResetOptions::ABORT_TRANSFERS | ResetOptions::FLUSH_PIPES | ResetOptions::CLEAR_STREAMS;
+        let pipes = match options.bits() & pipe_steps.bits() {
+            0 => Vec::new(),
+            _ => self.available_pipes().unwrap_or_else(|e| {
+                record(Err(e));
+                Vec::new()
+            }),
+        };
+        if options.contains(ResetOptions::ABORT_TRANSFERS) {
+            pipes
+                .iter()
+                .for_each(|&pipe| record(self.abort_transfers(pipe)));
+        }
+        if options.contains(ResetOptions::FLUSH_PIPES) {
+            pipes
+                .iter()
+                .filter(|pipe| pipe.is_read_pipe())
+                .for_each(|&pipe| record(self.flush_pipe(pipe)));
+        }
+        if options.contains(ResetOptions::CLEAR_STREAMS) {
+            pipes
+                .iter()
+                .for_each(|&pipe| record(self.set_stream_size(pipe, None)));
+        }
+        if options.contains(ResetOptions::RESET_PORT) {
+            record(self.reset_port());
+        }
+        result
+    }
+
+    /// Power cycles the device port. This causes the device to be re-enumermated by the host.
+    /// Consumes the object, meaning the device must be re-opened, for example
+    /// using [`Device::power_cycle_and_reopen`] or [`DeviceInfo::wait_for_reopen`].
+    /// See also [`Device::reset_port`].
+    pub fn power_cycle_port(self) -> Result<()> {
+        // TODO: determine if device needs to be reopened.
+        unsafe { lib::FT_CycleDevicePort(self.handle) }
+    }
+
+    /// Power cycles the device port, then waits up to `wait` for the device to
+    /// be re-enumerated and reopens it by its serial number.
+    ///
+    /// # Errors
+    /// - [`D3xxError::Timeout`] if the device did not reappear within `wait`.
+    /// - Any error from [`Device::info`] or [`Device::power_cycle_port`].
+    /// - See [`DeviceInfo::wait_for_reopen`].
+    pub fn power_cycle_and_reopen(self, wait: Duration) -> Result<Device> {
+        let info = self.info()?;
+        self.power_cycle_port()?;
+        info.wait_for_reopen(wait)
+    }
+
+    /// Identifies the physical device for the given duration, blocking until done.
+    ///
+    /// GPIO 0 is toggled at 1 Hz (see [`GpioBlink`]). If the GPIO pins are not
+    /// available, for example because battery charging detection is enabled,
+    /// a marker is written to `Out0` instead (see [`PipeMarker`]), which
+    /// gateware can use to drive an indicator. Use [`Device::identify_with`]
+    /// to choose the method.
+    ///
+    /// # Errors
+    /// Any error from blinking other than [`D3xxError::NotSupported`] is
+    /// returned without writing the marker, so that it is never written into
+    /// the data stream unless the GPIO pins are unavailable.
+    pub fn identify(&self, duration: Duration) -> Result<()> {
+        match GpioBlink::default().identify(self, duration) {
+            Err(D3xxError::NotSupported) => PipeMarker::default().identify(self, duration),
+            result => result,
+        }
+    }
+
+    /// Identifies the physical device using the given strategy.
+    pub fn identify_with(
+        &self,
+        strategy: &impl IdentificationStrategy,
+        duration: Duration,
+    ) -> Result<()> {
+        strategy.identify(self, duration)
+    }
+
+    /// Capture the current state of the device, for comparison with a later
+    /// snapshot through [`DeviceSnapshot::diff`].
+    ///
+    /// Pipe configurations are read with [`Device::snapshot_pipe_config`], so
+    /// timeouts and stream sizes are those set through this handle, and the
+    /// latency timer is missing on platforms other than Windows.
+    pub fn snapshot(&self) -> Result<DeviceSnapshot> {
+        DeviceSnapshot::capture(self)
+    }
+
+    /// Configures the given GPIO pin as an input or output.
+    pub fn set_gpio_direction(&self, pin: GpioPin, direction: GpioDirection) -> Result<()> {
+        unsafe {
+            lib::FT_EnableGPIO(
+                self.handle,
+                pin.mask(),
+                (direction as c_ulong) << (pin as c_ulong),
+            )
+        }
+    }
+
+    /// Drives the given GPIO pin high or low.
+    ///
+    /// The pin must first be configured as an output using [`Device::set_gpio_direction`].
+    pub fn set_gpio_value(&self, pin: GpioPin, value: GpioValue) -> Result<()> {
+        unsafe {
+            lib::FT_WriteGPIO(
+                self.handle,
+                pin.mask(),
+                (value as c_ulong) << (pin as c_ulong),
+            )
+        }
+    }
+
+    /// Reads the current level of the given GPIO pin.
+    pub fn get_gpio_value(&self, pin: GpioPin) -> Result<GpioValue> {
+        let mut data: c_ulong = 0;
+        unsafe {
+            lib::FT_ReadGPIO(self.handle, ptr_mut(&mut data))?;
+        }
+        if data & pin.mask() == 0 {
+            Ok(GpioValue::Low)
+        } else {
+            Ok(GpioValue::High)
+        }
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = lib::FT_Close(self.handle);
+        }
+    }
+}
+
+/// Devices are equal if they share the same handle.
+impl PartialEq for Device {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl Eq for Device {}
+
+/// Shows the handle and the cached device list index without talking to
+/// the device, as this is also used by every type wrapping a device. Use
+/// [`Display`] or [`Device::info`] for the serial number and description.
+impl Debug for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Device")
+            .field("handle", &self.handle)
+            .field("index", &self.index.get())
+            .finish()
+    }
+}
+
+/// Opens the device, see [`Device::open`].
+impl TryFrom<&DeviceInfo> for Device {
+    type Error = D3xxError;
+
+    fn try_from(info: &DeviceInfo) -> Result<Self> {
+        Device::open(info)
+    }
+}
+
+/// Opens the device with the given serial number, see [`Device::open_with_serial_number`].
+impl TryFrom<&str> for Device {
+    type Error = D3xxError;
+
+    fn try_from(serial_number: &str) -> Result<Self> {
+        Device::open_with_serial_number(serial_number)
+    }
+}
+
+/// Shows the device information, see [`DeviceInfo`]'s `Display` impl.
+impl Display for Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.info() {
+            Ok(info) => write!(f, "{info}"),
+            Err(_) => write!(f, "Device(handle={:?})", self.handle),
+        }
+    }
+}
+
+/// Result of a throughput measurement.
+///
+/// Obtained through [`Device::measure_write_bandwidth`] or [`Device::measure_read_bandwidth`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandwidthReport {
+    /// Total number of bytes transferred.
+    pub bytes_transferred: u64,
+    /// Time spent transferring.
+    pub elapsed: Duration,
+    /// Throughput in bytes per second.
+    pub throughput_bps: f64,
+}
+
+impl BandwidthReport {
+    /// Size of each transfer. Large multiples of 32 KiB give the best throughput.
+    const TRANSFER_SIZE: usize = 32 * 1024 * 8;
+
+    /// Repeat `transfer` until `duration` has elapsed.
+    fn measure(duration: Duration, mut transfer: impl FnMut() -> Result<usize>) -> Result<Self> {
+        let start = std::time::Instant::now();
+        let mut bytes_transferred = 0;
+        while start.elapsed() < duration {
+            match transfer() {
+                Ok(n) => bytes_transferred += n as u64,
+                Err(D3xxError::Timeout) => (),
+                Err(e) => Err(e)?,
+            }
+        }
+        let elapsed = start.elapsed();
+        Ok(Self {
+            bytes_transferred,
+            elapsed,
+            throughput_bps: bytes_transferred as f64 / elapsed.as_secs_f64(),
+        })
+    }
+}
+
+// =============================================================================
+/// Holds device information regarding a D3XX device attached to the system.
+#[derive(Clone, Default)]
+pub struct DeviceInfo {
+    /// Index in the D3XX device list. This value changes when the list is rebuilt!
+    index: usize,
+    inner: types::FT_DEVICE_LIST_INFO_NODE,
+}
+
+impl DeviceInfo {
+    /// Create a new DeviceInfo object from a raw value. The index is the index in the D3XX
+    /// device info list.
+    fn new(index: usize, node: types::FT_DEVICE_LIST_INFO_NODE) -> DeviceInfo {
+        DeviceInfo { index, inner: node }
+    }
+
+    /// Get the entry at the given index in the D3XX device info list using
+    /// `FT_GetDeviceInfoDetail`. The list must already have been created.
+    fn from_index(index: usize) -> Result<DeviceInfo> {
+        let mut device_info = types::FT_DEVICE_LIST_INFO_NODE::default();
+        unsafe {
+            lib::FT_GetDeviceInfoDetail(
+                index as c_ulong,
+                ptr_mut(&mut device_info.Flags),
+                ptr_mut(&mut device_info.Type),
+                ptr_mut(&mut device_info.ID),
+                ptr_mut(&mut device_info.LocId),
+                ptr_mut(&mut device_info.SerialNumber),
+                ptr_mut(&mut device_info.Description),
+                ptr_mut(&mut device_info.ftHandle),
+            )?;
+        }
+        Ok(DeviceInfo::new(index, device_info))
+    }
+
+    /// Look up the device with the given raw handle in the D3XX device list.
+    ///
+    /// The handle is only compared against those in the list and never
+    /// dereferenced, so it does not need to be valid; a closed or unknown
+    /// handle simply isn't found. The returned information, including its
+    /// index, is a snapshot which becomes stale when devices are connected or
+    /// disconnected.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::DeviceNotFound`] if no listed device has the handle.
+    pub fn from_handle(handle: types::FT_HANDLE) -> Result<DeviceInfo> {
+        list_devices()?
+            .into_iter()
+            .find(|info| info.raw_handle() == Some(handle))
+            .ok_or(D3xxError::DeviceNotFound)
+    }
+
+    /// Attempts to open the device represented by this struct.
+    pub fn open(&self) -> Result<Device> {
+        Device::try_from(self)
+    }
+
+    /// Open the device again by its serial number.
+    ///
+    /// Unlike the index, the serial number does not change when the device list
+    /// is rebuilt, so this is the way to recover after [`Device::power_cycle_port`]
+    /// or a [`D3xxError::DeviceNotConnected`] error.
+    pub fn reopen(&self) -> Result<Device> {
+        Device::open_with_serial_number(&self.serial_number()?)
+    }
+
+    /// Repeatedly try to [`reopen`](DeviceInfo::reopen) the device every 100 ms
+    /// until it succeeds or `timeout` elapses.
+    ///
+    /// # Errors
+    /// - [`D3xxError::Timeout`] if the device did not reappear in time.
+    /// - Any error which is neither transient nor caused by the device being absent.
+    pub fn wait_for_reopen(&self, timeout: Duration) -> Result<Device> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            match self.reopen() {
+                Err(e) if e.is_fatal() || e.is_transient() => (),
+                result => return result,
+            }
+            let now = std::time::Instant::now();
+            if now >= deadline {
+                return Err(D3xxError::Timeout);
+            }
+            std::thread::sleep(POLL_INTERVAL.min(deadline - now));
+        }
+    }
+
+    /// Gets the index of this device in the current D3XX device list.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Bit flags for USB3 or USB2 connection, etc.
+    pub fn flags(&self) -> u32 {
+        self.inner.Flags as _
+    }
+
+    /// Bit flags for USB3 or USB2 connection, etc. with named accessors.
+    pub fn flags_parsed(&self) -> DeviceFlags {
+        DeviceFlags::new(self.flags())
+    }
+
+    /// Check if the device is connected at SuperSpeed (USB 3.0).
+    pub fn is_usb3(&self) -> bool {
+        self.flags_parsed().is_super_speed()
+    }
+
+    /// Check if the device is connected at Hi-Speed (USB 2.0).
+    pub fn is_high_speed(&self) -> bool {
+        self.flags_parsed().is_high_speed()
+    }
+
+    /// The speed the device is connected at.
+    pub fn speed(&self) -> DeviceSpeed {
+        self.flags_parsed().speed()
+    }
+
+    /// Device type.
+    pub fn type_(&self) -> u32 {
+        self.inner.Type as _
+    }
+
+    /// Vendor ID.
+    pub fn vendor_id(&self) -> u16 {
+        ((self.inner.ID >> 16) & 0xFFFF) as _
+    }
+
+    /// Product ID.
+    pub fn product_id(&self) -> u16 {
+        (self.inner.ID & 0xFFFF) as _
+    }
+
+    /// Location identifier.
     pub fn location_identifier(&self) -> u32 {
         self.inner.LocId as _
     }
 
-    /// Device description.
-    pub fn description(&self) -> Result<String> {
-        types::c_str_to_string(&self.inner.Description)
+    /// Device description.
+    pub fn description(&self) -> Result<String> {
+        types::c_str_to_string(&self.inner.Description)
+    }
+
+    /// Device serial number.
+    pub fn serial_number(&self) -> Result<String> {
+        types::c_str_to_string(&self.inner.SerialNumber)
+    }
+
+    /// Raw handle to the device.
+    /// Returns `None` if the device is not opened, or `Some(handle)` if the device
+    /// is currently open.
+    pub fn raw_handle(&self) -> Option<types::FT_HANDLE> {
+        if self.inner.ftHandle as usize == 0 {
+            None
+        } else {
+            Some(self.inner.ftHandle)
+        }
+    }
+
+    /// Checks if the device is currently in use.
+    pub fn is_open(&self) -> bool {
+        self.raw_handle().is_some()
+    }
+
+    /// The raw serial number, up to the nul terminator.
+    fn serial_number_bytes(&self) -> &[u8] {
+        let serial = &self.inner.SerialNumber;
+        let len = serial.iter().position(|&b| b == 0).unwrap_or(serial.len());
+        &serial[..len]
+    }
+}
+
+/// Shows the chip, serial number, description and VID:PID on a single line,
+/// e.g. `FT601 [000000000001] 'FTDI SuperSpeed-FIFO Bridge' (0403:601F)`.
+impl Display for DeviceInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} [{}] '{}' ({:04X}:{:04X})",
+            DeviceVariant::from_device_type(self.type_()),
+            String::from_utf8_lossy(self.serial_number_bytes()),
+            self.description().unwrap_or_default(),
+            self.vendor_id(),
+            self.product_id()
+        )
+    }
+}
+
+impl Debug for DeviceInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeviceInfo")
+            .field(
+                "serial_number",
+                &String::from_utf8_lossy(self.serial_number_bytes()),
+            )
+            .field("description", &self.description().unwrap_or_default())
+            .field(
+                "id",
+                &format_args!("{:04X}:{:04X}", self.vendor_id(), self.product_id()),
+            )
+            .field("index", &self.index)
+            .field("type", &self.type_())
+            .field("location_identifier", &self.location_identifier())
+            .field("flags", &self.flags_parsed())
+            .field("handle", &self.inner.ftHandle)
+            .finish()
+    }
+}
+
+/// Device information is compared by serial number, which identifies the
+/// physical device regardless of its position in the device list.
+impl PartialEq for DeviceInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.serial_number_bytes() == other.serial_number_bytes()
+    }
+}
+
+impl Eq for DeviceInfo {}
+
+impl Hash for DeviceInfo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.serial_number_bytes().hash(state);
+    }
+}
+
+// =============================================================================
+/// Bit flags reported for a device in the D3XX device list.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct DeviceFlags(u32);
+
+impl DeviceFlags {
+    /// Create a new set of flags from a raw value.
+    pub fn new(flags: u32) -> DeviceFlags {
+        DeviceFlags(flags)
+    }
+
+    /// The raw flags value.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// The device is currently open.
+    pub fn is_open(&self) -> bool {
+        self.0 & constants::FT_FLAGS_OPENED as u32 != 0
+    }
+
+    /// The device is connected at Hi-Speed (USB 2.0).
+    pub fn is_high_speed(&self) -> bool {
+        self.0 & constants::FT_FLAGS_HISPEED as u32 != 0
+    }
+
+    /// The device is connected at SuperSpeed (USB 3.0).
+    pub fn is_super_speed(&self) -> bool {
+        self.0 & constants::FT_FLAGS_SUPERSPEED as u32 != 0
+    }
+
+    /// The speed the device is connected at.
+    pub fn speed(&self) -> DeviceSpeed {
+        let mask = (constants::FT_FLAGS_HISPEED | constants::FT_FLAGS_SUPERSPEED) as u32;
+        match self.0 & mask {
+            0 => DeviceSpeed::FullSpeed,
+            x if x == constants::FT_FLAGS_HISPEED as u32 => DeviceSpeed::HighSpeed,
+            x if x == constants::FT_FLAGS_SUPERSPEED as u32 => DeviceSpeed::SuperSpeed,
+            x => DeviceSpeed::Unknown(x),
+        }
+    }
+}
+
+/// Set of recovery steps performed by [`Device::drain_and_reset`].
+///
+/// Steps can be combined using `|`:
+///
+/// ```
+/// use ft60x_rs::ResetOptions;
+///
+/// let options = ResetOptions::default() | ResetOptions::RESET_PORT;
+/// assert!(options.contains(ResetOptions::FLUSH_PIPES));
+/// ```
+///
+/// The default is every step which can be performed while the device stays
+/// online, which excludes [`ResetOptions::RESET_PORT`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ResetOptions(u8);
+
+impl ResetOptions {
+    /// No steps.
+    pub const NONE: ResetOptions = ResetOptions(0);
+    /// Abort pending transfers on every exposed pipe.
+    pub const ABORT_TRANSFERS: ResetOptions = ResetOptions(1 << 0);
+    /// Discard data cached in every exposed IN pipe.
+    pub const FLUSH_PIPES: ResetOptions = ResetOptions(1 << 1);
+    /// Clear the stream size of every exposed pipe.
+    pub const CLEAR_STREAMS: ResetOptions = ResetOptions(1 << 2);
+    /// Reset the device port after the other steps.
+    pub const RESET_PORT: ResetOptions = ResetOptions(1 << 3);
+
+    /// The raw bits.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Check if all of the given steps are set.
+    pub fn contains(&self, other: ResetOptions) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Enable the given steps.
+    pub fn insert(&mut self, other: ResetOptions) {
+        self.0 |= other.0;
+    }
+
+    /// Disable the given steps.
+    pub fn remove(&mut self, other: ResetOptions) {
+        self.0 &= !other.0;
+    }
+}
+
+impl Default for ResetOptions {
+    fn default() -> Self {
+        ResetOptions::ABORT_TRANSFERS | ResetOptions::FLUSH_PIPES | ResetOptions::CLEAR_STREAMS
+    }
+}
+
+impl BitOr for ResetOptions {
+    type Output = ResetOptions;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        ResetOptions(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ResetOptions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A member of the FT60x family.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DeviceVariant {
+    /// FT600, with a 16-bit FIFO bus. Its default product ID is `0x601E`.
+    FT600,
+    /// FT601, with a 32-bit FIFO bus. Its default product ID is `0x601F`.
+    FT601,
+    /// The variant could not be determined.
+    Unknown,
+}
+
+impl DeviceVariant {
+    /// Get the variant from the device type reported in the D3XX device list.
+    fn from_device_type(type_: u32) -> DeviceVariant {
+        match type_ {
+            600 => DeviceVariant::FT600,
+            601 => DeviceVariant::FT601,
+            _ => DeviceVariant::Unknown,
+        }
+    }
+
+    /// Get the variant from FTDI's default product ID.
+    ///
+    /// A custom product ID set through the chip configuration gives [`DeviceVariant::Unknown`].
+    pub fn from_product_id(product_id: u16) -> DeviceVariant {
+        match product_id {
+            0x601E => DeviceVariant::FT600,
+            0x601F => DeviceVariant::FT601,
+            _ => DeviceVariant::Unknown,
+        }
     }
 
-    /// Device serial number.
-    pub fn serial_number(&self) -> Result<String> {
-        types::c_str_to_string(&self.inner.SerialNumber)
+    /// The maximum number of `(IN, OUT)` pipes, or `(0, 0)` for an unknown variant.
+    pub fn max_pipes(&self) -> (usize, usize) {
+        match self {
+            DeviceVariant::FT600 => (2, 2),
+            DeviceVariant::FT601 => (4, 4),
+            DeviceVariant::Unknown => (0, 0),
+        }
     }
+}
 
-    /// Raw handle to the device.
-    /// Returns `None` if the device is not opened, or `Some(handle)` if the device
-    /// is currently open.
-    pub fn raw_handle(&self) -> Option<types::FT_HANDLE> {
-        if self.inner.ftHandle as usize == 0 {
-            None
-        } else {
-            Some(self.inner.ftHandle)
+impl Display for DeviceVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DeviceVariant::FT600 => "FT600",
+            DeviceVariant::FT601 => "FT601",
+            DeviceVariant::Unknown => "FT60x",
+        })
+    }
+}
+
+/// The USB speed a device is connected at.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DeviceSpeed {
+    /// Full-Speed (USB 1.1, 12 Mbit/s).
+    FullSpeed,
+    /// Hi-Speed (USB 2.0, 480 Mbit/s).
+    HighSpeed,
+    /// SuperSpeed (USB 3.0, 5 Gbit/s).
+    SuperSpeed,
+    /// An unrecognized combination of speed flags.
+    Unknown(u32),
+}
+
+/// A USB specification version, as reported in a device descriptor.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum UsbVersion {
+    /// USB 1.1.
+    Usb11,
+    /// USB 2.0.
+    Usb20,
+    /// USB 3.0.
+    Usb30,
+    /// USB 3.1.
+    Usb31,
+    /// USB 3.2.
+    Usb32,
+    /// Any other version. Holds the raw BCD value.
+    Unknown(u16),
+}
+
+impl UsbVersion {
+    /// Decode a BCD version number, such as `0x0300` for USB 3.0.
+    pub fn from_bcd(bcd: u16) -> UsbVersion {
+        match bcd {
+            0x0110 => UsbVersion::Usb11,
+            // USB 2.0 devices supporting link power management report 2.01 or 2.1.
+            0x0200 | 0x0201 | 0x0210 => UsbVersion::Usb20,
+            0x0300 => UsbVersion::Usb30,
+            0x0310 => UsbVersion::Usb31,
+            0x0320 => UsbVersion::Usb32,
+            other => UsbVersion::Unknown(other),
         }
     }
 
-    /// Checks if the device is currently in use.
-    pub fn is_open(&self) -> bool {
-        self.raw_handle().is_some()
+    /// Check if the version supports SuperSpeed (USB 3.0 or later).
+    pub fn is_superspeed(&self) -> bool {
+        self.generation() >= 3
+    }
+
+    /// The major version number, such as 3 for USB 3.1.
+    pub fn generation(&self) -> u8 {
+        match self {
+            UsbVersion::Usb11 => 1,
+            UsbVersion::Usb20 => 2,
+            UsbVersion::Usb30 | UsbVersion::Usb31 | UsbVersion::Usb32 => 3,
+            UsbVersion::Unknown(bcd) => (bcd >> 8) as u8,
+        }
     }
 }
 
@@ -379,11 +2464,18 @@ pub struct DeviceDescriptor {
 }
 
 impl DeviceDescriptor {
-    /// The USB specification number the device complies to.
+    /// The USB specification number the device complies to, in BCD.
+    ///
+    /// See [`DeviceDescriptor::usb_version`] for the decoded version.
     pub fn usb_specification_number(&self) -> usize {
         self.inner.bcdUSB as _
     }
 
+    /// The USB specification version the device complies to.
+    pub fn usb_version(&self) -> UsbVersion {
+        UsbVersion::from_bcd(self.inner.bcdUSB)
+    }
+
     /// Check if the device is connected using a USB2 cable.
     pub fn is_usb2(&self) -> bool {
         (self.usb_specification_number() >> 8) & 0xFF == 2
@@ -433,6 +2525,38 @@ impl DeviceDescriptor {
     pub fn num_configurations(&self) -> usize {
         self.inner.bNumConfigurations as _
     }
+
+    /// Index of the string descriptor describing the manufacturer, or 0 if there is none.
+    pub fn manufacturer_string_index(&self) -> u8 {
+        self.inner.iManufacturer
+    }
+
+    /// Index of the string descriptor describing the product, or 0 if there is none.
+    pub fn product_string_index(&self) -> u8 {
+        self.inner.iProduct
+    }
+
+    /// Index of the string descriptor holding the serial number, or 0 if there is none.
+    pub fn serial_number_string_index(&self) -> u8 {
+        self.inner.iSerialNumber
+    }
+}
+
+/// Shows the USB version, VID:PID and number of configurations, e.g.
+/// `USB 3.0 device 0403:601F with 1 configuration(s)`.
+impl Display for DeviceDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bcd = self.usb_specification_number();
+        write!(
+            f,
+            "USB {}.{} device {:04X}:{:04X} with {} configuration(s)",
+            (bcd >> 8) & 0xFF,
+            (bcd >> 4) & 0xF,
+            self.vendor_id(),
+            self.product_id(),
+            self.num_configurations()
+        )
+    }
 }
 
 impl Debug for DeviceDescriptor {
@@ -441,46 +2565,632 @@ impl Debug for DeviceDescriptor {
     }
 }
 
-// =============================================================================
-/// Represents a pipe used for communication with a D3XX device.
-#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
-pub enum Pipe {
-    /// Input pipe 0 (0x82).
-    In0 = 0x82,
-    /// Input pipe 1 (0x83).
-    In1 = 0x83,
-    /// Input pipe 2 (0x84).
-    In2 = 0x84,
-    /// Input pipe 3 (0x85).
-    In3 = 0x85,
-    /// Output pipe 0 (0x02).
-    Out0 = 0x02,
-    /// Output pipe 1 (0x03).
-    Out1 = 0x03,
-    /// Output pipe 2 (0x04).
-    Out2 = 0x04,
-    /// Output pipe 3 (0x05).
-    Out3 = 0x05,
-}
+/// A USB string descriptor, holding a UTF-16 string.
+///
+/// The decoded string is obtained through its [`Display`] implementation,
+/// e.g. with `to_string()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringDescriptor {
+    chars: Vec<u16>,
+}
+
+impl StringDescriptor {
+    /// Parse a string descriptor as received from the device.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidFrame`] if the descriptor is malformed.
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let len = *bytes.first().ok_or_else(D3xxError::malformed_descriptor)? as usize;
+        let data = bytes
+            .get(2..len)
+            .ok_or_else(D3xxError::malformed_descriptor)?;
+        let chars = data
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        Ok(Self { chars })
+    }
+
+    /// The raw UTF-16 code units of the string.
+    pub fn as_utf16(&self) -> &[u16] {
+        &self.chars
+    }
+}
+
+impl Display for StringDescriptor {
+    /// Invalid UTF-16 is replaced with the replacement character.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        char::decode_utf16(self.chars.iter().copied())
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .try_for_each(|c| write!(f, "{}", c))
+    }
+}
+
+/// A USB language ID, as used for string descriptors.
+///
+/// See [`Device::supported_language_ids`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct LanguageId(pub u16);
+
+impl LanguageId {
+    /// Get the locale of the language, e.g. `en-US` for `0x0409`, or
+    /// `unknown` if the language is not known to this crate.
+    pub fn to_locale_string(&self) -> &'static str {
+        match self.0 {
+            0x0404 => "zh-TW",
+            0x0407 => "de-DE",
+            0x0409 => "en-US",
+            0x040A => "es-ES",
+            0x040C => "fr-FR",
+            0x0410 => "it-IT",
+            0x0411 => "ja-JP",
+            0x0412 => "ko-KR",
+            0x0413 => "nl-NL",
+            0x0416 => "pt-BR",
+            0x0419 => "ru-RU",
+            0x041D => "sv-SE",
+            0x0804 => "zh-CN",
+            0x0809 => "en-GB",
+            0x0816 => "pt-PT",
+            0x0C0A => "es-ES",
+            _ => "unknown",
+        }
+    }
+}
+
+impl From<u16> for LanguageId {
+    fn from(id: u16) -> Self {
+        LanguageId(id)
+    }
+}
+
+impl From<LanguageId> for u16 {
+    fn from(id: LanguageId) -> Self {
+        id.0
+    }
+}
+
+/// Holds information regarding a USB configuration.
+#[derive(Default, Clone)]
+pub struct ConfigurationDescriptor {
+    inner: types::FT_CONFIGURATION_DESCRIPTOR,
+    /// Whether the descriptor was read over a SuperSpeed connection,
+    /// which changes the unit of the max power field.
+    super_speed: bool,
+}
+
+impl ConfigurationDescriptor {
+    /// The total length of the configuration, including all interface and
+    /// endpoint descriptors.
+    pub fn total_length(&self) -> usize {
+        self.inner.wTotalLength as _
+    }
+
+    /// The number of interfaces supported by this configuration.
+    pub fn num_interfaces(&self) -> usize {
+        self.inner.bNumInterfaces as _
+    }
+
+    /// Get the descriptors of every interface in this configuration.
+    /// See [`Device::interface_descriptor`].
+    pub fn interfaces(&self, device: &Device) -> Result<Vec<InterfaceDescriptor>> {
+        (0..self.inner.bNumInterfaces)
+            .map(|index| device.interface_descriptor(index))
+            .collect()
+    }
+
+    /// The value used to select this configuration.
+    pub fn configuration_value(&self) -> u8 {
+        self.inner.bConfigurationValue as _
+    }
+
+    /// The index of the string descriptor describing this configuration.
+    pub fn configuration_string_index(&self) -> u8 {
+        self.inner.iConfiguration as _
+    }
+
+    /// The raw configuration characteristics bitmap.
+    pub fn attributes(&self) -> u8 {
+        self.inner.bmAttributes as _
+    }
+
+    /// Check if the device is self-powered in this configuration.
+    pub fn is_self_powered(&self) -> bool {
+        self.attributes() & 0x40 != 0
+    }
+
+    /// Check if the device supports remote wakeup in this configuration.
+    pub fn supports_remote_wakeup(&self) -> bool {
+        self.attributes() & 0x20 != 0
+    }
+
+    /// The raw maximum power consumption from the bus. This is in units of
+    /// 2 mA for USB2 connections and 8 mA for USB3 connections.
+    pub fn max_power(&self) -> u8 {
+        self.inner.MaxPower as _
+    }
+
+    /// The maximum power consumption from the bus in milliamps.
+    pub fn max_power_milliamps(&self) -> u16 {
+        let unit = if self.super_speed { 8 } else { 2 };
+        self.max_power() as u16 * unit
+    }
+}
+
+impl Debug for ConfigurationDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// Holds information regarding a USB interface.
+#[derive(Default, Clone)]
+pub struct InterfaceDescriptor {
+    inner: types::FT_INTERFACE_DESCRIPTOR,
+}
+
+impl InterfaceDescriptor {
+    /// The number of this interface.
+    pub fn interface_number(&self) -> u8 {
+        self.inner.bInterfaceNumber
+    }
+
+    /// The alternate setting selected for this interface.
+    pub fn alternate_setting(&self) -> u8 {
+        self.inner.bAlternateSetting
+    }
+
+    /// The number of endpoints used by this interface, excluding endpoint zero.
+    pub fn num_endpoints(&self) -> usize {
+        self.inner.bNumEndpoints as _
+    }
+
+    /// The interface class code assigned by the USB organization.
+    pub fn class_code(&self) -> u8 {
+        self.inner.bInterfaceClass
+    }
+
+    /// The interface subclass code assigned by the USB organization.
+    pub fn subclass_code(&self) -> u8 {
+        self.inner.bInterfaceSubClass
+    }
+
+    /// The interface protocol code assigned by the USB organization.
+    pub fn protocol_code(&self) -> u8 {
+        self.inner.bInterfaceProtocol
+    }
+
+    /// Index of the string descriptor describing this interface, or 0 if there is none.
+    pub fn string_index(&self) -> u8 {
+        self.inner.iInterface
+    }
+
+    /// Get the descriptors of the endpoints used by this interface, read from
+    /// the device's configuration descriptor.
+    pub fn endpoints(&self, device: &Device) -> Result<Vec<EndpointDescriptor>> {
+        let buf = device.raw_configuration_descriptor()?;
+        let mut endpoints = Vec::new();
+        let mut in_interface = false;
+        let mut rest = buf.as_slice();
+        while let [len, type_, ..] = *rest {
+            let Some(descriptor) = rest.get(..len as usize).filter(|_| len >= 2) else {
+                break;
+            };
+            match (type_, descriptor) {
+                (constants::FT_INTERFACE_DESCRIPTOR_TYPE, [_, _, number, alternate, ..]) => {
+                    in_interface = (*number, *alternate)
+                        == (self.interface_number(), self.alternate_setting());
+                }
+                (
+                    constants::FT_ENDPOINT_DESCRIPTOR_TYPE,
+                    [_, _, address, attributes, size_lo, size_hi, interval, ..],
+                ) if in_interface => endpoints.push(EndpointDescriptor {
+                    endpoint_address: *address,
+                    attributes: *attributes,
+                    max_packet_size: u16::from_le_bytes([*size_lo, *size_hi]),
+                    interval: *interval,
+                }),
+                _ => (),
+            }
+            rest = &rest[len as usize..];
+        }
+        Ok(endpoints)
+    }
+}
+
+impl Debug for InterfaceDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+/// Holds information regarding a USB endpoint.
+///
+/// Obtained through [`InterfaceDescriptor::endpoints`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EndpointDescriptor {
+    endpoint_address: u8,
+    attributes: u8,
+    max_packet_size: u16,
+    interval: u8,
+}
+
+impl EndpointDescriptor {
+    /// The endpoint address, with bit 7 set for IN endpoints.
+    pub fn endpoint_address(&self) -> u8 {
+        self.endpoint_address
+    }
+
+    /// The pipe using this endpoint, or `None` if it is not one of the
+    /// application pipes (see [`ReservedPipe`]).
+    pub fn pipe(&self) -> Option<Pipe> {
+        Pipe::from_endpoint_address(self.endpoint_address)
+    }
+
+    /// The raw `bmAttributes` field.
+    pub fn attributes(&self) -> u8 {
+        self.attributes
+    }
+
+    /// The transfer type, from the low bits of `bmAttributes`.
+    pub fn transfer_type(&self) -> PipeType {
+        PipeType::try_from(self.attributes & 0x03).expect("two bits always form a valid pipe type")
+    }
+
+    /// The maximum packet size in bytes.
+    pub fn max_packet_size(&self) -> usize {
+        self.max_packet_size as _
+    }
+
+    /// The polling interval. Used for interrupt and isochronous endpoints only.
+    pub fn interval(&self) -> u8 {
+        self.interval
+    }
+}
+
+/// The Binary Object Store (BOS) descriptor, listing the capabilities of a
+/// USB 2.1 or later device.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BosDescriptor {
+    total_length: u16,
+    num_device_caps: u8,
+    capabilities: Vec<DeviceCapabilityDescriptor>,
+}
+
+impl BosDescriptor {
+    /// Parse a BOS descriptor along with its capability descriptors.
+    fn from_bytes(bytes: &[u8]) -> Result<BosDescriptor> {
+        let [len, _, total_lo, total_hi, num_device_caps, ..] = *bytes else {
+            Err(D3xxError::malformed_descriptor())?
+        };
+        if len < 5 {
+            Err(D3xxError::malformed_descriptor())?;
+        }
+        let mut capabilities = Vec::new();
+        let mut rest = bytes.get(len as usize..).unwrap_or_default();
+        while let [len, _, type_, ..] = *rest {
+            let Some(descriptor) = rest.get(3..len as usize) else {
+                break;
+            };
+            capabilities.push(DeviceCapabilityDescriptor {
+                type_: CapabilityType::from(type_),
+                data: descriptor.to_vec(),
+            });
+            rest = &rest[len as usize..];
+        }
+        Ok(Self {
+            total_length: u16::from_le_bytes([total_lo, total_hi]),
+            num_device_caps,
+            capabilities,
+        })
+    }
+
+    /// The total length of the BOS descriptor and its capabilities in bytes.
+    pub fn total_length(&self) -> u16 {
+        self.total_length
+    }
+
+    /// The number of capabilities reported by the device.
+    pub fn num_device_caps(&self) -> u8 {
+        self.num_device_caps
+    }
+
+    /// The capability descriptors.
+    pub fn capabilities(&self) -> &[DeviceCapabilityDescriptor] {
+        &self.capabilities
+    }
+
+    /// Find the SuperSpeed USB capability, if the device reports one.
+    pub fn superspeed_capability(&self) -> Option<SuperSpeedCapability> {
+        self.capabilities
+            .iter()
+            .filter(|cap| cap.type_() == CapabilityType::SuperSpeedUsb)
+            .find_map(|cap| SuperSpeedCapability::from_data(cap.data()))
+    }
+}
+
+/// A device capability descriptor within a [`BosDescriptor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceCapabilityDescriptor {
+    type_: CapabilityType,
+    data: Vec<u8>,
+}
+
+impl DeviceCapabilityDescriptor {
+    /// The type of capability.
+    pub fn type_(&self) -> CapabilityType {
+        self.type_
+    }
+
+    /// The capability-specific bytes following `bDevCapabilityType`.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// The type of a [`DeviceCapabilityDescriptor`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum CapabilityType {
+    /// Wireless USB capability.
+    WirelessUsb,
+    /// USB 2.0 extension, describing link power management support.
+    Usb20Extension,
+    /// SuperSpeed USB capability.
+    SuperSpeedUsb,
+    /// Container ID, uniquely identifying the device.
+    ContainerId,
+    /// Platform-specific capability.
+    Platform,
+    /// SuperSpeedPlus USB capability.
+    SuperSpeedPlus,
+    /// Any other capability type. Holds the raw value.
+    Other(u8),
+}
+
+impl From<u8> for CapabilityType {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => CapabilityType::WirelessUsb,
+            0x02 => CapabilityType::Usb20Extension,
+            0x03 => CapabilityType::SuperSpeedUsb,
+            0x04 => CapabilityType::ContainerId,
+            0x05 => CapabilityType::Platform,
+            0x0A => CapabilityType::SuperSpeedPlus,
+            other => CapabilityType::Other(other),
+        }
+    }
+}
+
+/// The SuperSpeed USB device capability.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SuperSpeedCapability {
+    attributes: u8,
+    speeds_supported: u16,
+    functionality_support: u8,
+    u1_exit_latency: u8,
+    u2_exit_latency: u16,
+}
+
+impl SuperSpeedCapability {
+    /// Parse the capability-specific bytes of the descriptor.
+    fn from_data(data: &[u8]) -> Option<SuperSpeedCapability> {
+        let [attributes, speeds_lo, speeds_hi, functionality_support, u1_exit_latency, u2_lo, u2_hi, ..] =
+            *data
+        else {
+            return None;
+        };
+        Some(Self {
+            attributes,
+            speeds_supported: u16::from_le_bytes([speeds_lo, speeds_hi]),
+            functionality_support,
+            u1_exit_latency,
+            u2_exit_latency: u16::from_le_bytes([u2_lo, u2_hi]),
+        })
+    }
+
+    /// Check if the device supports Latency Tolerance Messages.
+    pub fn is_ltm_capable(&self) -> bool {
+        self.attributes & 0x02 != 0
+    }
+
+    /// Bitmap of the speeds supported: bit 0 is Low-Speed, bit 1 Full-Speed,
+    /// bit 2 Hi-Speed, and bit 3 SuperSpeed.
+    pub fn speeds_supported(&self) -> u16 {
+        self.speeds_supported
+    }
+
+    /// The lowest speed at which all functionality is available, using the
+    /// same numbering as [`SuperSpeedCapability::speeds_supported`].
+    pub fn functionality_support(&self) -> u8 {
+        self.functionality_support
+    }
+
+    /// Worst-case latency to exit the U1 link state, in microseconds.
+    pub fn u1_exit_latency(&self) -> u8 {
+        self.u1_exit_latency
+    }
+
+    /// Worst-case latency to exit the U2 link state, in microseconds.
+    pub fn u2_exit_latency(&self) -> u16 {
+        self.u2_exit_latency
+    }
+}
+
+// =============================================================================
+/// Represents a pipe used for communication with a D3XX device.
+///
+/// Only the bulk pipes available to applications are represented, so the
+/// transfer methods of [`Device`] cannot reach the pipes reserved by D3XX
+/// (see [`ReservedPipe`]).
+///
+/// Pipes are ordered by their endpoint address, so every OUT pipe sorts before
+/// every IN pipe. Use [`Pipe::ordered_by_direction_then_index`] as a sort key to
+/// put IN pipes first instead.
+///
+/// ```
+/// use ft60x_rs::Pipe;
+///
+/// assert!(Pipe::Out0 < Pipe::Out1 && Pipe::Out1 < Pipe::Out2 && Pipe::Out2 < Pipe::Out3);
+/// assert!(Pipe::In0 < Pipe::In1 && Pipe::In1 < Pipe::In2 && Pipe::In2 < Pipe::In3);
+/// assert!(Pipe::Out3 < Pipe::In0);
+///
+/// let mut pipes = vec![Pipe::Out1, Pipe::In1, Pipe::Out0, Pipe::In0];
+/// pipes.sort();
+/// assert_eq!(pipes, [Pipe::Out0, Pipe::Out1, Pipe::In0, Pipe::In1]);
+/// pipes.sort_by_key(Pipe::ordered_by_direction_then_index);
+/// assert_eq!(pipes, [Pipe::In0, Pipe::In1, Pipe::Out0, Pipe::Out1]);
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pipe {
+    /// Input pipe 0 (0x82).
+    In0 = 0x82,
+    /// Input pipe 1 (0x83).
+    In1 = 0x83,
+    /// Input pipe 2 (0x84).
+    In2 = 0x84,
+    /// Input pipe 3 (0x85).
+    In3 = 0x85,
+    /// Output pipe 0 (0x02).
+    Out0 = 0x02,
+    /// Output pipe 1 (0x03).
+    Out1 = 0x03,
+    /// Output pipe 2 (0x04).
+    Out2 = 0x04,
+    /// Output pipe 3 (0x05).
+    Out3 = 0x05,
+}
+
+impl Pipe {
+    /// Iterate over all pipes, IN pipes first.
+    pub fn all() -> impl Iterator<Item = Pipe> {
+        Self::read_pipes().chain(Self::write_pipes())
+    }
+
+    /// Iterate over all IN pipes.
+    pub fn read_pipes() -> impl Iterator<Item = Pipe> {
+        [Pipe::In0, Pipe::In1, Pipe::In2, Pipe::In3].into_iter()
+    }
+
+    /// Iterate over all OUT pipes.
+    pub fn write_pipes() -> impl Iterator<Item = Pipe> {
+        [Pipe::Out0, Pipe::Out1, Pipe::Out2, Pipe::Out3].into_iter()
+    }
+
+    /// Iterate over the pipes reserved by D3XX, which are not represented by
+    /// [`Pipe`]. See [`ReservedPipe`].
+    pub fn reserved_pipes() -> impl Iterator<Item = ReservedPipe> {
+        [ReservedPipe::Session, ReservedPipe::Notification].into_iter()
+    }
+
+    /// The USB endpoint address of the pipe.
+    ///
+    /// Bit 7 holds the direction (set for IN pipes, clear for OUT pipes) and
+    /// the low bits hold the endpoint number. The FT60x uses endpoints 2-5 for
+    /// pipes 0-3 in each direction, so `In0` is `0x82` and `Out0` is `0x02`.
+    pub fn to_endpoint_address(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Get the pipe with the given endpoint address, or `None` if no pipe uses it.
+    ///
+    /// This is the inverse of [`Pipe::to_endpoint_address`].
+    pub fn from_endpoint_address(address: u8) -> Option<Pipe> {
+        Pipe::try_from(address).ok()
+    }
+
+    /// Get the pipe with the given endpoint address.
+    ///
+    /// # Errors
+    /// Returns [`ParsePipeError`] if no pipe uses the address.
+    pub fn try_from_endpoint_address(address: u8) -> Result<Pipe, ParsePipeError> {
+        Self::from_endpoint_address(address)
+            .ok_or_else(|| ParsePipeError(format!("{address:#04x}")))
+    }
+
+    /// Create a pipe from its direction and its index (0-3).
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `index` is greater than 3.
+    pub fn from_parts(direction: PipeDirection, index: u8) -> Result<Pipe> {
+        if index > 3 {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        match direction {
+            PipeDirection::In => Pipe::try_from(Pipe::In0 as u8 + index),
+            PipeDirection::Out => Pipe::try_from(Pipe::Out0 as u8 + index),
+        }
+    }
+
+    /// The index of the pipe (0-3) within its direction.
+    pub fn index(&self) -> u8 {
+        match self.direction() {
+            PipeDirection::In => *self as u8 - Pipe::In0 as u8,
+            PipeDirection::Out => *self as u8 - Pipe::Out0 as u8,
+        }
+    }
+
+    /// A sort key ordering IN pipes before OUT pipes, and then by index.
+    ///
+    /// This matches the order of [`Pipe::all`].
+    pub fn ordered_by_direction_then_index(&self) -> impl Ord {
+        (self.direction() == PipeDirection::Out, self.index())
+    }
+
+    /// The direction of the pipe.
+    pub fn direction(&self) -> PipeDirection {
+        match self {
+            Pipe::In0 | Pipe::In1 | Pipe::In2 | Pipe::In3 => PipeDirection::In,
+            Pipe::Out0 | Pipe::Out1 | Pipe::Out2 | Pipe::Out3 => PipeDirection::Out,
+        }
+    }
+
+    /// The pipe with the same index in the opposite direction, e.g. `Out0` for `In0`.
+    ///
+    /// Every pipe has a companion, but whether a device exposes it depends on
+    /// its channel configuration; see [`Device::pipe_info_all`].
+    pub fn companion(&self) -> Pipe {
+        match self {
+            Pipe::In0 => Pipe::Out0,
+            Pipe::In1 => Pipe::Out1,
+            Pipe::In2 => Pipe::Out2,
+            Pipe::In3 => Pipe::Out3,
+            Pipe::Out0 => Pipe::In0,
+            Pipe::Out1 => Pipe::In1,
+            Pipe::Out2 => Pipe::In2,
+            Pipe::Out3 => Pipe::In3,
+        }
+    }
+
+    /// Position of the pipe in [`Pipe::all`], for indexing per-pipe arrays.
+    fn slot(&self) -> usize {
+        match self.direction() {
+            PipeDirection::In => self.index() as usize,
+            PipeDirection::Out => 4 + self.index() as usize,
+        }
+    }
 
-impl Pipe {
     /// Check if the pipe is a read pipe.
     pub fn is_read_pipe(&self) -> bool {
-        match self {
-            Pipe::In0 | Pipe::In1 | Pipe::In2 | Pipe::In3 => true,
-            Pipe::Out0 | Pipe::Out1 | Pipe::Out2 | Pipe::Out3 => false,
-        }
+        self.direction() == PipeDirection::In
     }
 
     /// Check if the pipe is a write pipe.
     pub fn is_write_pipe(&self) -> bool {
-        match self {
-            Pipe::In0 | Pipe::In1 | Pipe::In2 | Pipe::In3 => false,
-            Pipe::Out0 | Pipe::Out1 | Pipe::Out2 | Pipe::Out3 => true,
-        }
+        self.direction() == PipeDirection::Out
     }
 }
 
+/// The direction of a [`Pipe`], from the host's point of view.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PipeDirection {
+    /// Device to host.
+    In,
+    /// Host to device.
+    Out,
+}
+
 impl Debug for Pipe {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let value = *self as u8;
@@ -497,27 +3207,132 @@ impl Debug for Pipe {
     }
 }
 
-impl From<u8> for Pipe {
+impl Display for Pipe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::In0 => "In0",
+            Self::In1 => "In1",
+            Self::In2 => "In2",
+            Self::In3 => "In3",
+            Self::Out0 => "Out0",
+            Self::Out1 => "Out1",
+            Self::Out2 => "Out2",
+            Self::Out3 => "Out3",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Pipe {
+    type Err = ParsePipeError;
+
+    /// Parse a pipe from its name (e.g. `In0`, `out3`; case-insensitive) or from
+    /// its endpoint address in hex notation (e.g. `0x82`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePipeError(s.to_owned());
+        let trimmed = s.trim();
+        if let Some(hex) = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+        {
+            let address = u8::from_str_radix(hex, 16).map_err(|_| error())?;
+            return Pipe::from_endpoint_address(address).ok_or_else(error);
+        }
+        Pipe::all()
+            .find(|pipe| pipe.to_string().eq_ignore_ascii_case(trimmed))
+            .ok_or_else(error)
+    }
+}
+
+impl TryFrom<u8> for Pipe {
+    type Error = D3xxError;
+
     /// Convert from a raw pipe ID to a `Pipe` enum.
     ///
-    /// # Panics
-    /// Panics if the given value is an invalid pipe ID.
-    fn from(pipe_id: u8) -> Self {
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the given value is an invalid pipe ID.
+    fn try_from(pipe_id: u8) -> Result<Self> {
         match pipe_id {
-            0x82 => Pipe::In0,
-            0x83 => Pipe::In1,
-            0x84 => Pipe::In2,
-            0x85 => Pipe::In3,
-            0x02 => Pipe::Out0,
-            0x03 => Pipe::Out1,
-            0x04 => Pipe::Out2,
-            0x05 => Pipe::Out3,
-            _ => panic!("Unknown value: {}", pipe_id),
+            0x82 => Ok(Pipe::In0),
+            0x83 => Ok(Pipe::In1),
+            0x84 => Ok(Pipe::In2),
+            0x85 => Ok(Pipe::In3),
+            0x02 => Ok(Pipe::Out0),
+            0x03 => Ok(Pipe::Out1),
+            0x04 => Ok(Pipe::Out2),
+            0x05 => Ok(Pipe::Out3),
+            _ => Err(D3xxError::InvalidParameter),
+        }
+    }
+}
+
+/// A pipe reserved by D3XX for managing the chip.
+///
+/// These pipes are deliberately not part of [`Pipe`]. The session pipe carries
+/// D3XX's own management commands, and writing to it from an application can
+/// corrupt the state of the chip; see [`Device::session_write`]. The
+/// notification pipe can be read safely using [`Device::read_notification`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ReservedPipe {
+    /// The OUT pipe used for session management commands (`0x01`).
+    Session = constants::FT_RESERVED_PIPE_SESSION as isize,
+    /// The IN pipe used for notification messages (`0x81`).
+    Notification = constants::FT_RESERVED_PIPE_NOTIFICATION as isize,
+}
+
+impl ReservedPipe {
+    /// The USB endpoint address of the pipe.
+    pub fn endpoint_address(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Get the reserved pipe with the given endpoint address, or `None` if the
+    /// address is not reserved.
+    pub fn from_endpoint_address(address: u8) -> Option<ReservedPipe> {
+        Pipe::reserved_pipes().find(|pipe| pipe.endpoint_address() == address)
+    }
+}
+
+/// A matched IN and OUT pipe, for request-response style communication.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipePair {
+    /// The pipe responses are read from.
+    pub input: Pipe,
+    /// The pipe requests are written to.
+    pub output: Pipe,
+}
+
+impl PipePair {
+    /// Create a new pair from an IN and an OUT pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `input` is not an IN pipe or
+    /// `output` is not an OUT pipe.
+    pub fn new(input: Pipe, output: Pipe) -> Result<PipePair> {
+        if !input.is_read_pipe() || !output.is_write_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(Self { input, output })
+    }
+
+    /// The pair of [`Pipe::In0`] and [`Pipe::Out0`].
+    pub fn default_pair() -> PipePair {
+        Self {
+            input: Pipe::In0,
+            output: Pipe::Out0,
         }
     }
 }
 
+impl Default for PipePair {
+    fn default() -> Self {
+        Self::default_pair()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PipeType {
     /// USB control pipe
     Control = 0,
@@ -529,18 +3344,20 @@ pub enum PipeType {
     Interrupt = 3,
 }
 
-impl From<u8> for PipeType {
+impl TryFrom<u8> for PipeType {
+    type Error = D3xxError;
+
     /// Convert from a raw pipe type to a `PipeType` enum.
     ///
-    /// # Panics
-    /// Panics if the given value is an invalid pipe type.
-    fn from(pipe_id: u8) -> Self {
-        match pipe_id {
-            0 => PipeType::Control,
-            1 => PipeType::Isochronous,
-            2 => PipeType::Bulk,
-            3 => PipeType::Interrupt,
-            _ => panic!("Unknown value: {}", pipe_id),
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the given value is an invalid pipe type.
+    fn try_from(pipe_type: u8) -> Result<Self> {
+        match pipe_type {
+            0 => Ok(PipeType::Control),
+            1 => Ok(PipeType::Isochronous),
+            2 => Ok(PipeType::Bulk),
+            3 => Ok(PipeType::Interrupt),
+            _ => Err(D3xxError::InvalidParameter),
         }
     }
 }
@@ -549,17 +3366,26 @@ impl From<u8> for PipeType {
 #[derive(Default, Clone, Copy, Eq, PartialEq)]
 pub struct PipeInfo {
     inner: types::FT_PIPE_INFORMATION,
+    /// `bMaxBurst` and `bmAttributes` from the SuperSpeed endpoint companion
+    /// descriptor, if the pipe has one.
+    companion: Option<(u8, u8)>,
 }
 
 impl PipeInfo {
     /// Get the type of pipe.
-    pub fn type_(&self) -> PipeType {
-        PipeType::from(self.inner.PipeType as u8)
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the device reported an unknown pipe type.
+    pub fn type_(&self) -> Result<PipeType> {
+        PipeType::try_from(self.inner.PipeType as u8)
     }
 
     /// Get the pipe.
-    pub fn pipe(&self) -> Pipe {
-        Pipe::from(self.inner.PipeID as u8)
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the device reported an unknown pipe ID.
+    pub fn pipe(&self) -> Result<Pipe> {
+        Pipe::try_from(self.inner.PipeID)
     }
 
     /// Get the maximum transfer size for this pipe.
@@ -571,6 +3397,72 @@ impl PipeInfo {
     pub fn interval(&self) -> u8 {
         self.inner.Interval as _
     }
+
+    /// Check if this is a control pipe.
+    pub fn is_control(&self) -> bool {
+        self.type_().ok() == Some(PipeType::Control)
+    }
+
+    /// Check if this is an isochronous pipe.
+    pub fn is_isochronous(&self) -> bool {
+        self.type_().ok() == Some(PipeType::Isochronous)
+    }
+
+    /// Check if this is a bulk pipe.
+    pub fn is_bulk(&self) -> bool {
+        self.type_().ok() == Some(PipeType::Bulk)
+    }
+
+    /// Check if this is an interrupt pipe.
+    pub fn is_interrupt(&self) -> bool {
+        self.type_().ok() == Some(PipeType::Interrupt)
+    }
+
+    /// Get the number of bytes the pipe can move in a single burst.
+    ///
+    /// At SuperSpeed this is the maximum packet size multiplied by the burst
+    /// count and, for isochronous pipes, the number of bursts per interval,
+    /// as given by the endpoint companion descriptor. At other speeds, or when
+    /// the companion descriptor is unknown, it is the maximum packet size.
+    pub fn max_burst_size(&self) -> usize {
+        let Some((max_burst, attributes)) = self.companion else {
+            return self.maximum_packet_size();
+        };
+        // For bulk pipes the low bits of bmAttributes hold the number of
+        // streams instead.
+        let mult = if self.is_isochronous() {
+            (attributes & 0x03) as usize + 1
+        } else {
+            1
+        };
+        self.maximum_packet_size() * (max_burst as usize + 1) * mult
+    }
+
+    /// The minimum granularity of efficient transfers on this pipe, which is
+    /// the maximum packet size.
+    ///
+    /// Transfer sizes which are not a multiple of this end in a short packet.
+    pub fn transfer_size_hint(&self) -> usize {
+        self.maximum_packet_size()
+    }
+
+    /// A transfer size giving good throughput on this pipe.
+    ///
+    /// This is 32 KiB, the size recommended for the FT601, rounded up to a
+    /// multiple of the maximum packet size. Larger multiples can improve
+    /// throughput further at the cost of latency. The FT600 may prefer
+    /// smaller transfers; see [`Device::set_usb_transfer_sizes`].
+    pub fn optimal_transfer_size(&self) -> usize {
+        self.round_up_to_packet(32 * 1024)
+    }
+
+    /// Round `size` up to the next multiple of the maximum packet size.
+    pub fn round_up_to_packet(&self, size: usize) -> usize {
+        match self.maximum_packet_size() {
+            0 => size,
+            packet_size => size.next_multiple_of(packet_size),
+        }
+    }
 }
 
 impl Debug for PipeInfo {
@@ -579,10 +3471,156 @@ impl Debug for PipeInfo {
     }
 }
 
+/// Information about the pipes exposed by a device, indexed by [`Pipe`].
+///
+/// Obtained through [`Device::pipe_info_all`].
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct PipeInfoMap {
+    pipes: [Option<PipeInfo>; PipeInfoMap::CAPACITY],
+}
+
+impl PipeInfoMap {
+    const CAPACITY: usize = 8;
+
+    /// Get information about the given pipe, if the device exposes it.
+    pub fn get(&self, pipe: Pipe) -> Option<&PipeInfo> {
+        self.pipes[pipe.slot()].as_ref()
+    }
+
+    /// The number of pipes exposed by the device.
+    pub fn len(&self) -> usize {
+        self.pipes.iter().flatten().count()
+    }
+
+    /// Check if the device exposes no pipes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over the exposed pipes, IN pipes first.
+    pub fn iter(&self) -> std::iter::Flatten<std::slice::Iter<'_, Option<PipeInfo>>> {
+        self.pipes.iter().flatten()
+    }
+}
+
+impl IntoIterator for PipeInfoMap {
+    type Item = PipeInfo;
+    type IntoIter = std::iter::Flatten<std::array::IntoIter<Option<PipeInfo>, 8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.pipes.into_iter().flatten()
+    }
+}
+
+impl<'a> IntoIterator for &'a PipeInfoMap {
+    type Item = &'a PipeInfo;
+    type IntoIter = std::iter::Flatten<std::slice::Iter<'a, Option<PipeInfo>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Settings for a single pipe, applied with [`Device::apply_pipe_config`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipeConfig {
+    /// The pipe timeout. See [`Device::set_timeout`].
+    pub timeout: Duration,
+    /// The stream size, or `None` to disable streaming. See [`Device::set_stream_size`].
+    pub stream_size: Option<u32>,
+    /// The latency timer in milliseconds, or `None` to leave it unchanged.
+    /// See [`Device::set_latency_timer`].
+    pub latency_ms: Option<u8>,
+}
+
+// =============================================================================
+/// A GPIO pin on a D3XX device.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
+pub enum GpioPin {
+    /// GPIO 0.
+    Pin0 = constants::FT_GPIO_0 as isize,
+    /// GPIO 1.
+    Pin1 = constants::FT_GPIO_1 as isize,
+}
+
+impl GpioPin {
+    /// Bit mask selecting this pin in the D3XX GPIO functions.
+    fn mask(&self) -> c_ulong {
+        1 << (*self as c_ulong)
+    }
+}
+
+/// The direction of a GPIO pin.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GpioDirection {
+    /// The pin is used as an input.
+    Input = constants::FT_GPIO_DIRECTION_IN as isize,
+    /// The pin is used as an output.
+    Output = constants::FT_GPIO_DIRECTION_OUT as isize,
+}
+
+/// The logic level of a GPIO pin.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GpioValue {
+    /// Logic low.
+    Low = constants::FT_GPIO_VALUE_LOW as isize,
+    /// Logic high.
+    High = constants::FT_GPIO_VALUE_HIGH as isize,
+}
+
+/// Parse a GUID in the format `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`.
+fn parse_guid(s: &str) -> Option<types::GUID> {
+    let s = s.strip_prefix('{')?.strip_suffix('}')?;
+    let groups: Vec<&str> = s.split('-').collect();
+    let [d1, d2, d3, d4, d5] = groups[..] else {
+        return None;
+    };
+    let lengths = [d1.len(), d2.len(), d3.len(), d4.len(), d5.len()];
+    if lengths != [8, 4, 4, 4, 12] || !s.chars().all(|c| c == '-' || c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let tail = format!("{d4}{d5}");
+    let mut data4 = [0; 8];
+    for (i, byte) in data4.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&tail[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(types::GUID {
+        Data1: u32::from_str_radix(d1, 16).ok()?,
+        Data2: u16::from_str_radix(d2, 16).ok()?,
+        Data3: u16::from_str_radix(d3, 16).ok()?,
+        Data4: data4,
+    })
+}
+
+/// The kind of driver used to access D3XX devices. See [`d3xx_driver_type`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DriverType {
+    /// The kernel-mode driver (`FTD3XX.sys`) used on Windows.
+    KernelMode,
+    /// The user-mode driver built into the D3XX library on Linux and macOS.
+    UserMode,
+    /// The driver type is not known for this platform.
+    Unknown,
+}
+
 // =============================================================================
 
 /// Represents a D3XX driver or library version number.
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Versions are ordered by major, minor, subversion and then build number:
+///
+/// ```
+/// use ft60x_rs::Version;
+///
+/// assert!(Version::new(0x01030010) > Version::new(0x01020010));
+/// assert!(Version::new(0x01020010) > Version::new(0x0102000F));
+/// assert!(Version::new(0x0100FFFF) < Version::new(0x01010000));
+/// assert!(Version::new(0x00FFFFFF) < Version::new(0x01000000));
+/// assert!(Version::new(0x01020304) == "1.2.3.4".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Version {
     major: u8,
     minor: u8,
@@ -601,6 +3639,16 @@ impl Version {
         }
     }
 
+    /// Create a new version from its individual parts.
+    fn from_parts(major: u8, minor: u8, svn: u8, build: u8) -> Version {
+        Self {
+            major,
+            minor,
+            svn,
+            build,
+        }
+    }
+
     /// Major version number.
     pub fn major(&self) -> u8 {
         self.major
@@ -620,6 +3668,46 @@ impl Version {
     pub fn build(&self) -> u8 {
         self.build
     }
+
+    /// Check if this version is at least `major.minor`, ignoring the other fields.
+    pub fn is_at_least(&self, major: u8, minor: u8) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+
+    /// Check if this version is at least `other`, comparing all fields.
+    pub fn is_at_least_full(&self, other: &Version) -> bool {
+        self >= other
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.svn, self.build
+        )
+    }
+}
+
+impl FromStr for Version {
+    type Err = ParseVersionError;
+
+    /// Parse a version in the format `major.minor[.svn[.build]]`.
+    /// The subversion and build numbers default to zero if omitted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseVersionError(s.to_owned());
+        let parts = s
+            .split('.')
+            .map(|part| part.trim().parse::<u8>().map_err(|_| error()))
+            .collect::<Result<Vec<_>, _>>()?;
+        match parts[..] {
+            [major, minor] => Ok(Self::from_parts(major, minor, 0, 0)),
+            [major, minor, svn] => Ok(Self::from_parts(major, minor, svn, 0)),
+            [major, minor, svn, build] => Ok(Self::from_parts(major, minor, svn, build)),
+            _ => Err(error()),
+        }
+    }
 }
 
 // =============================================================================
@@ -627,28 +3715,181 @@ impl Version {
 pub fn device_count() -> Result<u32> {
     let mut n: c_ulong = 0;
     unsafe {
-        lib::FT_ListDevices(ptr_mut(&mut n), null_mut(), constants::FT_LIST_NUMBER_ONLY)?;
+        lib::FT_CreateDeviceInfoList(ptr_mut(&mut n))?;
     }
     Ok(n as u32)
 }
 
+/// Get the number of connected D3XX devices with the given vendor and product IDs.
+///
+/// D3XX can only count all devices, so this lists them and filters the result.
+pub fn device_count_by_vid_pid(vid: u16, pid: u16) -> Result<u32> {
+    count_devices(|info| info.vendor_id() == vid && info.product_id() == pid)
+}
+
+/// Get the number of connected D3XX devices which are currently open, by this
+/// or any other process.
+pub fn device_count_open() -> Result<u32> {
+    count_devices(DeviceInfo::is_open)
+}
+
+/// Get the number of connected D3XX devices which are not currently open.
+pub fn device_count_available() -> Result<u32> {
+    count_devices(|info| !info.is_open())
+}
+
+/// Count the connected devices matching the given predicate.
+fn count_devices(predicate: impl Fn(&DeviceInfo) -> bool) -> Result<u32> {
+    Ok(list_devices()?
+        .iter()
+        .filter(|info| predicate(info))
+        .count() as u32)
+}
+
 /// Get information about all D3XX devices connected to the system.
 pub fn list_devices() -> Result<Vec<DeviceInfo>> {
-    const MAX_DEVICES: usize = 32;
-    let mut num_devices = 0;
-    let devices = unsafe {
-        let mut devices: [types::FT_DEVICE_LIST_INFO_NODE; MAX_DEVICES] = std::mem::zeroed();
+    list_devices_max(usize::MAX)
+}
+
+/// Get information about at most `max` D3XX devices connected to the system.
+pub fn list_devices_max(max: usize) -> Result<Vec<DeviceInfo>> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("d3xx::list_devices").entered();
+    let mut num_devices: c_ulong = 0;
+    unsafe {
         lib::FT_CreateDeviceInfoList(ptr_mut(&mut num_devices))?;
-        lib::FT_GetDeviceInfoList(ptr_mut(&mut devices), ptr_mut(&mut num_devices))?;
-        devices
-    };
-    Ok(devices[..num_devices]
-        .iter()
+    }
+    if num_devices == 0 {
+        return Ok(Vec::new());
+    }
+    let mut devices = vec![types::FT_DEVICE_LIST_INFO_NODE::default(); num_devices as usize];
+    unsafe {
+        lib::FT_GetDeviceInfoList(devices.as_mut_ptr(), ptr_mut(&mut num_devices))?;
+    }
+    devices.truncate((num_devices as usize).min(max));
+    Ok(devices
+        .into_iter()
         .enumerate()
-        .map(|(i, e)| DeviceInfo::new(i, e.clone()))
+        .map(|(i, e)| DeviceInfo::new(i, e))
         .collect())
 }
 
+/// Lazily get information about the D3XX devices connected to the system.
+///
+/// The device list is created once, and each entry is only fetched when the
+/// iterator reaches it. This avoids fetching the whole list when searching
+/// for a single device:
+///
+/// ```no_run
+/// let device = ft60x_rs::iter_devices()?
+///     .filter_map(Result::ok)
+///     .find(|info| info.serial_number().is_ok_and(|s| s == "000000000001"));
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+pub fn iter_devices() -> Result<DeviceIter> {
+    let mut count: c_ulong = 0;
+    unsafe {
+        lib::FT_CreateDeviceInfoList(ptr_mut(&mut count))?;
+    }
+    Ok(DeviceIter {
+        index: 0,
+        count: count as usize,
+    })
+}
+
+/// Iterator over the D3XX devices connected to the system.
+///
+/// Created using [`iter_devices`]. The number of devices is fixed when the
+/// iterator is created, even if devices are connected or disconnected
+/// during iteration.
+#[derive(Debug, Clone)]
+pub struct DeviceIter {
+    index: usize,
+    count: usize,
+}
+
+impl Iterator for DeviceIter {
+    type Item = Result<DeviceInfo>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
+        }
+        self.index += 1;
+        Some(DeviceInfo::from_index(self.index - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for DeviceIter {}
+
+impl std::iter::FusedIterator for DeviceIter {}
+
+/// Get information about the device at the given index in the D3XX device list.
+///
+/// Unlike [`list_devices`], this does not rebuild the device list, so it is
+/// cheaper when the index is known from a previous enumeration. The index is
+/// only valid until the list is rebuilt, e.g. by [`list_devices`] or
+/// [`device_count`].
+///
+/// # Errors
+/// Returns an error if the list has not been built or the index is out of range.
+pub fn device_info_at_index(index: usize) -> Result<DeviceInfo> {
+    DeviceInfo::from_index(index)
+}
+
+/// Get the serial number of the device at the given index using `FT_ListDevices`.
+///
+/// This is an alternative to [`device_info_at_index`] which does not depend
+/// on the device list having been built beforehand. `FT_ListDevices` only
+/// reports a single string per device, so no other information is available.
+pub fn serial_number_at_index(index: usize) -> Result<String> {
+    let mut buf = [0u8; 64];
+    unsafe {
+        lib::FT_ListDevices(
+            index as *mut c_void,
+            buf.as_mut_ptr() as *mut c_void,
+            constants::FT_LIST_BY_INDEX | constants::FT_OPEN_BY_SERIAL_NUMBER,
+        )?;
+    }
+    types::c_str_to_string(&buf)
+}
+
+/// Get information about all connected D3XX devices with the given vendor and product IDs.
+pub fn list_devices_by_vid_pid(vid: u16, pid: u16) -> Result<Vec<DeviceInfo>> {
+    let mut devices = list_devices()?;
+    devices.retain(|info| info.vendor_id() == vid && info.product_id() == pid);
+    Ok(devices)
+}
+
+/// Get information about all connected D3XX devices whose description contains
+/// `description`, ignoring case.
+///
+/// Devices with a description which cannot be decoded are skipped.
+pub fn list_devices_by_description(description: &str) -> Result<Vec<DeviceInfo>> {
+    let description = description.to_lowercase();
+    let mut devices = list_devices()?;
+    devices.retain(|info| {
+        info.description()
+            .is_ok_and(|d| d.to_lowercase().contains(&description))
+    });
+    Ok(devices)
+}
+
+/// Get information about all connected D3XX devices whose serial number starts
+/// with `prefix`.
+///
+/// Devices with a serial number which cannot be decoded are skipped.
+pub fn list_devices_by_serial_prefix(prefix: &str) -> Result<Vec<DeviceInfo>> {
+    let mut devices = list_devices()?;
+    devices.retain(|info| info.serial_number().is_ok_and(|s| s.starts_with(prefix)));
+    Ok(devices)
+}
+
 /// Get the D3XX library version.
 pub fn d3xx_version() -> Version {
     let mut version: c_ulong = 0;
@@ -659,9 +3900,186 @@ pub fn d3xx_version() -> Version {
     Version::new(version as u32)
 }
 
+/// Get the kind of driver used to access D3XX devices.
+///
+/// D3XX provides no call to query this, but it is fixed for each platform:
+/// the Windows library talks to the `FTD3XX.sys` kernel driver, while the
+/// Linux and macOS libraries drive the device from user space through libusb.
+/// The same driver is used for every device on the system.
+pub fn d3xx_driver_type() -> DriverType {
+    if cfg!(windows) {
+        DriverType::KernelMode
+    } else if cfg!(any(target_os = "linux", target_os = "macos")) {
+        DriverType::UserMode
+    } else {
+        DriverType::Unknown
+    }
+}
+
+/// Check that the driver used by the device is at least version `major.minor`.
+///
+/// # Errors
+/// - [`D3xxError::NotSupported`] if the driver is older.
+/// - Any error from [`Device::driver_version`].
+pub fn require_driver_version(device: &Device, major: u8, minor: u8) -> Result<()> {
+    if !device.driver_version()?.is_at_least(major, minor) {
+        Err(D3xxError::NotSupported)?;
+    }
+    Ok(())
+}
+
+/// Check that the loaded D3XX library is at least version `major.minor`.
+///
+/// # Errors
+/// Returns [`D3xxError::NotSupported`] if the library is older.
+pub fn require_library_version(major: u8, minor: u8) -> Result<()> {
+    if !d3xx_version().is_at_least(major, minor) {
+        Err(D3xxError::NotSupported)?;
+    }
+    Ok(())
+}
+
+/// Check that a transfer length is a non-zero multiple of the packet size.
+fn check_alignment(len: usize, packet_size: usize) -> Result<()> {
+    if len == 0 || packet_size == 0 || !len.is_multiple_of(packet_size) {
+        Err(D3xxError::InvalidParameter)?;
+    }
+    Ok(())
+}
+
+/// Record the outcome of a transfer as an event in the current span.
+#[cfg(feature = "tracing")]
+fn trace_transfer(result: &Result<usize>) {
+    match result {
+        Ok(n) => tracing::debug!(bytes_transferred = n, "transfer complete"),
+        Err(e) => tracing::debug!(error = %e, "transfer failed"),
+    }
+}
+
 /// Check if D3XX drivers are available on this system.
 pub fn d3xx_available() -> bool {
     device_count().is_ok()
 }
 
+// The D3XX library allows a handle to be used from any thread, as long as it is
+// not used from several threads at the same time. `Device` is therefore `Send`,
+// but not `Sync`; sharing requires external synchronization such as a `Mutex`.
 unsafe impl Send for Device {}
+
+/// A copy of a device's raw handle, for transfers on one pipe which must not
+/// wait for transfers on other pipes, and for aborting transfers from another
+/// thread.
+///
+/// Unlike the [`Device`] methods, reads through this handle do not check
+/// alignment enforcement or record statistics. It must only be used while the
+/// device it was taken from is open.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SharedHandle(types::FT_HANDLE);
+
+impl SharedHandle {
+    /// Reads data from the specified pipe using `FT_ReadPipe`, aborting the
+    /// pipe if the read fails.
+    pub(crate) fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        let mut bytes_transferred = 0;
+        unsafe {
+            match lib::FT_ReadPipe(
+                self.0,
+                pipe as c_uchar,
+                buf as *mut _ as *mut u8,
+                buf.len() as c_ulong,
+                &mut bytes_transferred,
+                std::ptr::null_mut(),
+            ) {
+                Ok(_) => (),
+                Err(e) => {
+                    self.abort(pipe)?;
+                    return Err(e);
+                }
+            }
+        }
+        Ok(bytes_transferred as usize)
+    }
+
+    /// Reads a message from the notification pipe. See [`Device::read_notification`].
+    pub(crate) fn read_notification(&self, buf: &mut [u8]) -> Result<usize> {
+        let mut bytes_transferred = 0;
+        unsafe {
+            lib::FT_ReadPipe(
+                self.0,
+                ReservedPipe::Notification.endpoint_address(),
+                buf as *mut _ as *mut u8,
+                buf.len() as c_ulong,
+                &mut bytes_transferred,
+                std::ptr::null_mut(),
+            )?;
+        }
+        Ok(bytes_transferred as usize)
+    }
+
+    /// Aborts all pending transfers for the given pipe.
+    pub(crate) fn abort(&self, pipe: Pipe) -> Result<()> {
+        unsafe { lib::FT_AbortPipe(self.0, pipe as c_uchar) }
+    }
+}
+
+// D3XX allows transfers on different pipes of a device to run on different
+// threads at once, and aborting a pipe from another thread is how a blocking
+// transfer is cancelled. Callers of `SharedHandle` keep to one thread per pipe.
+unsafe impl Send for SharedHandle {}
+unsafe impl Sync for SharedHandle {}
+
+// `DeviceInfo` only uses its handle for comparisons and never dereferences it.
+unsafe impl Send for DeviceInfo {}
+unsafe impl Sync for DeviceInfo {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// Create information about a device as it would appear in the D3XX device list.
+    fn device_info(index: usize, serial_number: &str, handle: usize) -> DeviceInfo {
+        let mut node = types::FT_DEVICE_LIST_INFO_NODE::default();
+        node.SerialNumber[..serial_number.len()].copy_from_slice(serial_number.as_bytes());
+        node.ftHandle = handle as types::FT_HANDLE;
+        DeviceInfo::new(index, node)
+    }
+
+    #[test]
+    fn device_info_set_deduplicates_by_serial_number() {
+        let first = device_info(0, "000000000001", 0);
+        // The same device found again at another index, having been opened since.
+        let again = device_info(1, "000000000001", 0x1000);
+        let other = device_info(2, "000000000002", 0);
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+        let devices: HashSet<DeviceInfo> = [first.clone(), again, other].into_iter().collect();
+        assert_eq!(devices.len(), 2);
+        assert!(devices.contains(&first));
+    }
+
+    #[test]
+    fn flush_pipe_rejects_write_pipes() {
+        // The pipe is checked before the handle is used, so no device is needed.
+        let device = unsafe { Device::from_handle(std::ptr::null_mut()) };
+        for pipe in Pipe::write_pipes() {
+            assert!(matches!(
+                device.flush_pipe(pipe),
+                Err(D3xxError::InvalidParameter)
+            ));
+        }
+        // Don't close the null handle.
+        std::mem::forget(device);
+    }
+
+    #[test]
+    fn pipe_companion_has_same_index_and_opposite_direction() {
+        for pipe in Pipe::all() {
+            let companion = pipe.companion();
+            assert_eq!(companion.index(), pipe.index());
+            assert_ne!(companion.direction(), pipe.direction());
+            assert_eq!(companion.companion(), pipe);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Reading several IN pipes at once using one thread per pipe.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{D3xxError, Pipe, Result, SharedHandle, ThreadSafeDevice};
+
+/// How long [`MultiPipeReader::shutdown`] waits between attempts to abort the
+/// reads in progress.
+const ABORT_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A chunk of data read by a [`MultiPipeReader`], tagged with its pipe.
+pub type PipeData = (Pipe, Result<Vec<u8>>);
+
+/// Reads several IN pipes on background threads, sending everything read
+/// through a single channel.
+///
+/// Each pipe is read by its own thread. Timeouts and empty reads are skipped;
+/// any other error is sent through the channel and stops that pipe's thread.
+///
+/// The device is kept open through a [`ThreadSafeDevice`], but the reads do
+/// not take its lock, so a pipe with no data does not hold up the others.
+/// Reading the same pipes through the device at the same time is not
+/// supported. Reads are not counted in [`Device::stats`](crate::Device::stats).
+///
+/// ```no_run
+/// use ft60x_rs::{Device, MultiPipeReader, Pipe, ThreadSafeDevice};
+///
+/// let device = ThreadSafeDevice::new(Device::open_by_index(0)?);
+/// let reader = MultiPipeReader::new(device, vec![Pipe::In0, Pipe::In1])?;
+/// for (pipe, data) in reader.receiver().iter().take(10) {
+///     println!("{pipe}: {} bytes", data?.len());
+/// }
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug)]
+pub struct MultiPipeReader {
+    receiver: Receiver<PipeData>,
+    stop: Arc<AtomicBool>,
+    handle: SharedHandle,
+    threads: Mutex<Vec<(Pipe, JoinHandle<()>)>>,
+}
+
+impl MultiPipeReader {
+    /// The size of each read when using [`MultiPipeReader::new`].
+    pub const DEFAULT_READ_SIZE: usize = 32 * 1024;
+
+    /// Start reading the given pipes, [`MultiPipeReader::DEFAULT_READ_SIZE`]
+    /// bytes at a time.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if any pipe is not an IN pipe.
+    pub fn new(device: ThreadSafeDevice, pipes: Vec<Pipe>) -> Result<MultiPipeReader> {
+        Self::with_read_size(device, pipes, Self::DEFAULT_READ_SIZE)
+    }
+
+    /// Start reading the given pipes, `read_size` bytes at a time.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if any pipe is not an IN pipe,
+    /// `read_size` is zero, or `read_size` is not aligned on a pipe with
+    /// alignment enforcement enabled.
+    pub fn with_read_size(
+        device: ThreadSafeDevice,
+        pipes: Vec<Pipe>,
+        read_size: usize,
+    ) -> Result<MultiPipeReader> {
+        if read_size == 0 || !pipes.iter().all(Pipe::is_read_pipe) {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let handle = {
+            let device = device.lock();
+            for &pipe in &pipes {
+                device.check_enforced_alignment(pipe, read_size)?;
+            }
+            device.shared_handle()
+        };
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let stop = Arc::new(AtomicBool::new(false));
+        let threads = pipes
+            .into_iter()
+            .map(|pipe| {
+                let device = device.clone();
+                let sender = sender.clone();
+                let stop = Arc::clone(&stop);
+                let thread = std::thread::spawn(move || {
+                    read_loop(handle, pipe, read_size, sender, stop);
+                    // Keep the device open until the thread no longer uses its handle.
+                    drop(device);
+                });
+                (pipe, thread)
+            })
+            .collect();
+        Ok(Self {
+            receiver,
+            stop,
+            handle,
+            threads: Mutex::new(threads),
+        })
+    }
+
+    /// The channel receiving data from all pipes.
+    ///
+    /// The channel disconnects once every reader thread has stopped.
+    pub fn receiver(&self) -> &Receiver<PipeData> {
+        &self.receiver
+    }
+
+    /// Stop all reader threads, aborting the reads in progress.
+    ///
+    /// Data already sent through the channel can still be received. Data in
+    /// transit when the reads are aborted is lost.
+    pub fn shutdown(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let threads =
+            std::mem::take(&mut *self.threads.lock().unwrap_or_else(PoisonError::into_inner));
+        for (pipe, thread) in threads {
+            // A thread may start another read just after an abort, so keep
+            // aborting until it notices the stop flag.
+            while !thread.is_finished() {
+                let _ = self.handle.abort(pipe);
+                std::thread::sleep(ABORT_INTERVAL);
+            }
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MultiPipeReader {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Read from a single pipe until stopped, an error occurs, or the receiver is dropped.
+fn read_loop(
+    handle: SharedHandle,
+    pipe: Pipe,
+    read_size: usize,
+    sender: Sender<PipeData>,
+    stop: Arc<AtomicBool>,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        let mut buf = vec![0; read_size];
+        let result = match handle.read(pipe, &mut buf) {
+            // The read was aborted by `MultiPipeReader::shutdown`.
+            Err(_) if stop.load(Ordering::Relaxed) => return,
+            Ok(0) | Err(D3xxError::Timeout) => continue,
+            Ok(n) => {
+                buf.truncate(n);
+                Ok(buf)
+            }
+            Err(e) => Err(e),
+        };
+        let failed = result.is_err();
+        if sender.send((pipe, result)).is_err() || failed {
+            return;
+        }
+    }
+}
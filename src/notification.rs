@@ -0,0 +1,73 @@
+//! Reading of notification messages on a background thread.
+
+use std::{
+    sync::mpsc::{self, TryRecvError},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::{D3xxError, Result, ThreadSafeDevice};
+
+/// How long each read of the notification pipe waits, which bounds how
+/// quickly the listener stops.
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The largest notification message read at once.
+const MAX_MESSAGE_SIZE: usize = 64;
+
+/// Reads the notification pipe on a background thread, passing each message
+/// to a callback. See [`Device::read_notification`](crate::Device::read_notification).
+///
+/// The device is kept open through a [`ThreadSafeDevice`], but the reads do
+/// not take its lock, so the callback and other threads can use the device
+/// meanwhile. The background thread is stopped when the listener is dropped,
+/// or when reading fails with an error other than a timeout.
+#[derive(Debug)]
+pub struct NotificationListener {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl NotificationListener {
+    /// Start reading the notification pipe, calling `callback` with each message.
+    ///
+    /// # Errors
+    /// Any error from [`Device::set_notification_timeout`](crate::Device::set_notification_timeout).
+    pub fn spawn(
+        device: ThreadSafeDevice,
+        callback: impl Fn(Vec<u8>) + Send + 'static,
+    ) -> Result<NotificationListener> {
+        let handle = {
+            let device = device.lock();
+            device.set_notification_timeout(POLL_TIMEOUT)?;
+            device.shared_handle()
+        };
+        let (stop, stop_rx) = mpsc::channel();
+        let thread = std::thread::spawn(move || {
+            let mut buf = [0; MAX_MESSAGE_SIZE];
+            while let Err(TryRecvError::Empty) = stop_rx.try_recv() {
+                match handle.read_notification(&mut buf) {
+                    Ok(0) | Err(D3xxError::Timeout) => (),
+                    Ok(n) => callback(buf[..n].to_vec()),
+                    Err(_) => break,
+                }
+            }
+            // Keep the device open until the thread no longer uses its handle.
+            drop(device);
+        });
+        Ok(Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for NotificationListener {
+    fn drop(&mut self) {
+        // Dropping the sender tells the thread to stop after its current read.
+        self.stop.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! Overlapped transfers, available on Windows only.
+
+use std::time::{Duration, Instant};
+
+use libc::{c_uchar, c_ulong, c_void};
+
+use crate::{
+    ffi::{lib, types},
+    D3xxError, Device, Pipe, Result,
+};
+
+/// How often [`Transfer::wait`] checks whether the transfer has completed.
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// A transfer started with [`Device::begin_read`] or [`Device::begin_write`]
+/// which may still be in progress.
+///
+/// The transfer owns its buffer until it is finished, so the buffer cannot
+/// be touched or freed while the driver may still be using it. The buffer is
+/// handed back by [`Transfer::into_buffer`]. Dropping a transfer which has not
+/// finished aborts it and waits for the driver to let go of the buffer; if
+/// the transfer is leaked instead, so is the buffer.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use ft60x_rs::{Device, Pipe};
+///
+/// let device = Device::open_by_index(0)?;
+/// let mut transfer = device.begin_read(Pipe::In0, vec![0; 32 * 1024])?;
+/// // Do other work while the transfer is in progress...
+/// let n = transfer.wait(Duration::from_secs(1))?;
+/// let data = &transfer.into_buffer()[..n];
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug)]
+pub struct Transfer<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+    /// Boxed so its address stays fixed while the driver uses it.
+    state: Box<TransferState>,
+    /// The buffer being transferred. Its heap allocation stays fixed while
+    /// the driver uses it.
+    buf: Vec<u8>,
+    /// Whether the driver may still be using the buffer.
+    pending: bool,
+}
+
+#[derive(Debug, Default)]
+struct TransferState {
+    overlapped: types::OVERLAPPED,
+    bytes_transferred: c_ulong,
+}
+
+impl<'a> Transfer<'a> {
+    /// Start reading from an IN pipe into `buf`.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an IN pipe.
+    pub(crate) fn begin_read(device: &'a Device, pipe: Pipe, buf: Vec<u8>) -> Result<Self> {
+        if !pipe.is_read_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let mut transfer = Self::new(device, pipe, buf)?;
+        let result = unsafe {
+            lib::FT_ReadPipe(
+                device.handle,
+                pipe as c_uchar,
+                transfer.buf.as_mut_ptr(),
+                transfer.buf.len() as c_ulong,
+                &mut transfer.state.bytes_transferred,
+                transfer.overlapped_ptr(),
+            )
+        };
+        transfer.started(result)
+    }
+
+    /// Start writing `buf` to an OUT pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an OUT pipe.
+    pub(crate) fn begin_write(device: &'a Device, pipe: Pipe, buf: Vec<u8>) -> Result<Self> {
+        if !pipe.is_write_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let mut transfer = Self::new(device, pipe, buf)?;
+        let result = unsafe {
+            lib::FT_WritePipeEx(
+                device.handle,
+                pipe as c_uchar,
+                transfer.buf.as_ptr(),
+                transfer.buf.len() as c_ulong,
+                &mut transfer.state.bytes_transferred,
+                transfer.overlapped_ptr(),
+            )
+        };
+        transfer.started(result)
+    }
+
+    /// Initialize the overlapped structure for a new transfer.
+    fn new(device: &'a Device, pipe: Pipe, buf: Vec<u8>) -> Result<Self> {
+        let mut state = Box::<TransferState>::default();
+        unsafe {
+            lib::FT_InitializeOverlapped(
+                device.handle,
+                &mut state.overlapped as *mut _ as *mut c_void,
+            )?;
+        }
+        Ok(Self {
+            device,
+            pipe,
+            state,
+            buf,
+            pending: false,
+        })
+    }
+
+    /// Check the result of starting the transfer.
+    fn started(mut self, result: Result<()>) -> Result<Self> {
+        match result {
+            Ok(()) | Err(D3xxError::IoPending) => {
+                self.pending = true;
+                Ok(self)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn overlapped_ptr(&mut self) -> *mut c_void {
+        &mut self.state.overlapped as *mut _ as *mut c_void
+    }
+
+    /// The pipe being transferred on.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// Wait for the transfer to finish, returning the number of bytes transferred.
+    ///
+    /// For reads, the data is at the start of the buffer returned by
+    /// [`Transfer::into_buffer`].
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::Timeout`] if the transfer does not finish within
+    /// `timeout`, in which case it is aborted, and
+    /// [`D3xxError::InvalidParameter`] if the transfer has already finished or
+    /// been aborted. Otherwise, any error from the transfer itself.
+    pub fn wait(&mut self, timeout: Duration) -> Result<usize> {
+        if !self.pending {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.result(false) {
+                Err(D3xxError::IoPending | D3xxError::IoIncomplete) => {
+                    if Instant::now() >= deadline {
+                        self.abort();
+                        Err(D3xxError::Timeout)?;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                result => {
+                    self.pending = false;
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Get the result of the transfer, optionally blocking until it finishes.
+    fn result(&mut self, wait: bool) -> Result<usize> {
+        let mut bytes_transferred: c_ulong = 0;
+        unsafe {
+            lib::FT_GetOverlappedResult(
+                self.device.handle,
+                self.overlapped_ptr(),
+                &mut bytes_transferred,
+                wait as _,
+            )?;
+        }
+        Ok(bytes_transferred as usize)
+    }
+
+    /// Abort the transfer if it is still pending, waiting for the driver to
+    /// let go of the buffer.
+    fn abort(&mut self) {
+        if self.pending {
+            let _ = self.device.abort_transfers(self.pipe);
+            let _ = self.result(true);
+            self.pending = false;
+        }
+    }
+
+    /// Take back the buffer, aborting the transfer if it has not finished.
+    pub fn into_buffer(mut self) -> Vec<u8> {
+        self.abort();
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl Drop for Transfer<'_> {
+    fn drop(&mut self) {
+        self.abort();
+        unsafe {
+            let _ = lib::FT_ReleaseOverlapped(self.device.handle, self.overlapped_ptr());
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Standard library I/O adapters for D3XX pipes.
+
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{D3xxError, Device, Pipe, Result};
+
+/// Reads from an IN pipe through the [`std::io::Read`] trait.
+///
+/// Created using [`Device::pipe_reader`].
+#[derive(Debug)]
+pub struct PipeReader<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+}
+
+impl<'a> PipeReader<'a> {
+    /// Create a new reader over the given pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an IN pipe.
+    pub(crate) fn new(device: &'a Device, pipe: Pipe) -> Result<Self> {
+        if !pipe.is_read_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(Self { device, pipe })
+    }
+
+    /// The pipe being read from.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+}
+
+impl io::Read for PipeReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.device.read(self.pipe, buf).map_err(io::Error::from)
+    }
+}
+
+/// Writes to an OUT pipe through the [`std::io::Write`] trait.
+///
+/// Created using [`Device::pipe_writer`].
+#[derive(Debug)]
+pub struct PipeWriter<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+}
+
+impl<'a> PipeWriter<'a> {
+    /// Create a new writer over the given pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an OUT pipe.
+    pub(crate) fn new(device: &'a Device, pipe: Pipe) -> Result<Self> {
+        if !pipe.is_write_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(Self { device, pipe })
+    }
+
+    /// The pipe being written to.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+}
+
+impl io::Write for PipeWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.device.write(self.pipe, buf).map_err(io::Error::from)
+    }
+
+    /// Writes are not buffered, so this does nothing.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes to an OUT pipe through the [`std::io::Write`] trait, throttling
+/// throughput to a maximum number of bytes per second.
+///
+/// After each transfer, the writer sleeps for however long the transfer
+/// finished ahead of the allowed rate. Large buffers are sent as a single
+/// transfer, so keep them small relative to the rate for smooth throttling.
+///
+/// Created using [`Device::rate_limited_writer`].
+#[derive(Debug)]
+pub struct RateLimitedWriter<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+    max_bps: u64,
+    /// Time of the first write.
+    start: Option<Instant>,
+    bytes_written: u64,
+}
+
+impl<'a> RateLimitedWriter<'a> {
+    /// Create a new writer over the given pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an OUT pipe or
+    /// `max_bps` is zero.
+    pub(crate) fn new(device: &'a Device, pipe: Pipe, max_bps: u64) -> Result<Self> {
+        if !pipe.is_write_pipe() || max_bps == 0 {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(Self {
+            device,
+            pipe,
+            max_bps,
+            start: None,
+            bytes_written: 0,
+        })
+    }
+
+    /// The pipe being written to.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// The maximum throughput in bytes per second.
+    pub fn max_bps(&self) -> u64 {
+        self.max_bps
+    }
+
+    /// The average throughput since the first write in bytes per second,
+    /// including time spent throttling.
+    pub fn actual_throughput(&self) -> f64 {
+        match self.start.map(|start| start.elapsed().as_secs_f64()) {
+            Some(secs) if secs > 0.0 => self.bytes_written as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+impl io::Write for RateLimitedWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let started = Instant::now();
+        self.start.get_or_insert(started);
+        let n = self.device.write(self.pipe, buf)?;
+        self.bytes_written += n as u64;
+
+        let allowed = Duration::from_secs_f64(n as f64 / self.max_bps as f64);
+        if let Some(remaining) = allowed.checked_sub(started.elapsed()) {
+            std::thread::sleep(remaining);
+        }
+        Ok(n)
+    }
+
+    /// Writes are not buffered, so this does nothing.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads fixed-size frames from an IN pipe.
+///
+/// Each item is exactly one frame, read using [`Device::read_exact`]. The
+/// iterator never ends by itself; errors such as timeouts are yielded as items.
+///
+/// Created using [`Device::framed_reader`].
+#[derive(Debug)]
+pub struct FramedReader<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+    frame_size: usize,
+}
+
+impl<'a> FramedReader<'a> {
+    /// Create a new reader over the given pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an IN pipe, or
+    /// if `frame_size` is not a non-zero multiple of the pipe's maximum packet size.
+    pub(crate) fn new(device: &'a Device, pipe: Pipe, frame_size: usize) -> Result<Self> {
+        if !pipe.is_read_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let packet_size = device.pipe_info(pipe)?.maximum_packet_size();
+        if frame_size == 0 || !frame_size.is_multiple_of(packet_size) {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(Self {
+            device,
+            pipe,
+            frame_size,
+        })
+    }
+
+    /// The pipe being read from.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// The size of each frame in bytes.
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+}
+
+impl Iterator for FramedReader<'_> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut frame = vec![0; self.frame_size];
+        Some(self.device.read_exact(self.pipe, &mut frame).map(|_| frame))
+    }
+}
+
+/// Iterator over chunks read from an IN pipe.
+///
+/// Each item is the data returned by a single [`Device::read`] of up to
+/// `chunk_size` bytes. The iterator ends when a read returns no data or
+/// [`D3xxError::HandleEof`]; other errors, including timeouts, are yielded
+/// as items.
+///
+/// Created using [`Device::read_chunks`].
+#[derive(Debug)]
+pub struct ChunkedPipeReader<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+    chunk_size: usize,
+    finished: bool,
+}
+
+impl<'a> ChunkedPipeReader<'a> {
+    /// The SuperSpeed bulk packet size, which chunk sizes must be a multiple of.
+    const PACKET_SIZE: usize = 512;
+
+    /// Create a new reader over the given pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an IN pipe, or
+    /// if `chunk_size` is not a non-zero multiple of 512.
+    pub(crate) fn new(device: &'a Device, pipe: Pipe, chunk_size: usize) -> Result<Self> {
+        if !pipe.is_read_pipe() || chunk_size == 0 || !chunk_size.is_multiple_of(Self::PACKET_SIZE)
+        {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        Ok(Self {
+            device,
+            pipe,
+            chunk_size,
+            finished: false,
+        })
+    }
+
+    /// The pipe being read from.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// The maximum size of each chunk in bytes.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+}
+
+impl Iterator for ChunkedPipeReader<'_> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let mut chunk = vec![0; self.chunk_size];
+        match self.device.read(self.pipe, &mut chunk) {
+            Ok(0) | Err(D3xxError::HandleEof) => {
+                self.finished = true;
+                None
+            }
+            Ok(n) => {
+                chunk.truncate(n);
+                Some(Ok(chunk))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+impl std::iter::FusedIterator for ChunkedPipeReader<'_> {}
+
+/// Writes whole frames to an OUT pipe.
+///
+/// Created using [`Device::framed_writer`].
+#[derive(Debug)]
+pub struct FramedWriter<'a> {
+    device: &'a Device,
+    pipe: Pipe,
+    packet_size: usize,
+}
+
+impl<'a> FramedWriter<'a> {
+    /// Create a new writer over the given pipe.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an OUT pipe.
+    pub(crate) fn new(device: &'a Device, pipe: Pipe) -> Result<Self> {
+        if !pipe.is_write_pipe() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let packet_size = device.pipe_info(pipe)?.maximum_packet_size();
+        Ok(Self {
+            device,
+            pipe,
+            packet_size,
+        })
+    }
+
+    /// The pipe being written to.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// Write a single frame, issuing as many transfers as needed.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if the frame is empty or not a
+    /// multiple of the pipe's maximum packet size. Otherwise, see [`Device::write_all`].
+    pub fn write_frame(&mut self, frame: &[u8]) -> Result<()> {
+        if frame.is_empty() || !frame.len().is_multiple_of(self.packet_size) {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        self.device.write_all(self.pipe, frame)
+    }
+}
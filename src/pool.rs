@@ -0,0 +1,165 @@
+//! A pool of pre-opened devices shared between workers.
+
+use std::{
+    collections::VecDeque,
+    ops::Deref,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{D3xxError, Device, DeviceInfo, Result};
+
+/// A set of open devices which are handed out to one user at a time.
+///
+/// Devices are taken from the pool using [`DevicePool::acquire`] or
+/// [`DevicePool::try_acquire`], and go back into the pool when the returned
+/// [`DeviceGuard`] is dropped. A device which no longer responds when it is
+/// returned is reopened by its serial number; if that fails, it is removed
+/// from the pool.
+///
+/// ```no_run
+/// use ft60x_rs::{list_devices, DevicePool, Pipe};
+///
+/// let pool = DevicePool::new(list_devices()?)?;
+/// let device = pool.acquire()?;
+/// device.write(Pipe::Out0, &[0; 16])?;
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug)]
+pub struct DevicePool {
+    state: Mutex<PoolState>,
+    returned: Condvar,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    /// Devices not currently in use, along with their serial numbers.
+    idle: VecDeque<(String, Device)>,
+    /// Number of devices managed by the pool, including those in use.
+    len: usize,
+}
+
+impl DevicePool {
+    /// Open all of the given devices.
+    ///
+    /// # Errors
+    /// Any error from opening a device. Devices opened so far are closed.
+    pub fn new(devices: Vec<DeviceInfo>) -> Result<DevicePool> {
+        let idle = devices
+            .iter()
+            .map(|info| {
+                let serial_number = info.serial_number()?;
+                let device = Device::open_with_serial_number(&serial_number)?;
+                Ok((serial_number, device))
+            })
+            .collect::<Result<VecDeque<_>>>()?;
+        Ok(Self {
+            state: Mutex::new(PoolState {
+                len: idle.len(),
+                idle,
+            }),
+            returned: Condvar::new(),
+        })
+    }
+
+    /// Take a device from the pool, waiting until one is available.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::DeviceNotFound`] if the pool has no devices left.
+    pub fn acquire(&self) -> Result<DeviceGuard<'_>> {
+        let mut state = self.lock();
+        loop {
+            if let Some(entry) = state.idle.pop_front() {
+                return Ok(DeviceGuard {
+                    pool: self,
+                    entry: Some(entry),
+                });
+            }
+            if state.len == 0 {
+                Err(D3xxError::DeviceNotFound)?;
+            }
+            state = self
+                .returned
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Take a device from the pool if one is available right away.
+    pub fn try_acquire(&self) -> Option<DeviceGuard<'_>> {
+        let entry = self.lock().idle.pop_front()?;
+        Some(DeviceGuard {
+            pool: self,
+            entry: Some(entry),
+        })
+    }
+
+    /// The number of devices managed by the pool, including those in use.
+    pub fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    /// Check if the pool has no devices left.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of devices available to be acquired.
+    pub fn available(&self) -> usize {
+        self.lock().idle.len()
+    }
+
+    /// Put a device back into the pool, reopening it if it has failed.
+    fn release(&self, serial_number: String, device: Device) {
+        let device = match device.driver_version() {
+            Ok(_) => Some(device),
+            Err(_) => {
+                // The old handle must be closed before the device can be reopened.
+                drop(device);
+                Device::open_with_serial_number(&serial_number).ok()
+            }
+        };
+        let mut state = self.lock();
+        match device {
+            Some(device) => state.idle.push_back((serial_number, device)),
+            None => state.len -= 1,
+        }
+        // Waiters also need to know when the last device is removed.
+        self.returned.notify_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        // A panic while holding the lock cannot leave the state inconsistent,
+        // so poisoning is ignored.
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A device taken from a [`DevicePool`], which is returned to the pool when dropped.
+#[derive(Debug)]
+pub struct DeviceGuard<'a> {
+    pool: &'a DevicePool,
+    entry: Option<(String, Device)>,
+}
+
+impl DeviceGuard<'_> {
+    /// The serial number of the device.
+    pub fn serial_number(&self) -> &str {
+        &self.entry.as_ref().expect("entry is only taken on drop").0
+    }
+}
+
+impl Deref for DeviceGuard<'_> {
+    type Target = Device;
+
+    fn deref(&self) -> &Device {
+        &self.entry.as_ref().expect("entry is only taken on drop").1
+    }
+}
+
+impl Drop for DeviceGuard<'_> {
+    fn drop(&mut self) {
+        if let Some((serial_number, device)) = self.entry.take() {
+            self.pool.release(serial_number, device);
+        }
+    }
+}
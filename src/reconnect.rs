@@ -0,0 +1,195 @@
+//! Automatic reopening of a device after it is disconnected.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, MutexGuard, PoisonError, Weak,
+    },
+    time::Duration,
+};
+
+use crate::{D3xxError, Device, Pipe, Result};
+
+/// Controls how a [`ReconnectingDevice`] tries to reopen its device.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ReconnectPolicy {
+    /// The delay between attempts to reopen the device.
+    pub reconnect_interval: Duration,
+    /// The maximum number of attempts before giving up, or `None` to keep
+    /// trying indefinitely.
+    pub max_attempts: Option<usize>,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            reconnect_interval: Duration::from_millis(500),
+            max_attempts: None,
+        }
+    }
+}
+
+/// A change in the connection state of a [`ReconnectingDevice`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConnectionEvent {
+    /// The device was lost and is being reopened in the background.
+    Disconnected,
+    /// The device was reopened.
+    Reconnected,
+    /// The device could not be reopened within the maximum number of attempts.
+    ///
+    /// Another attempt is started by the next transfer.
+    ReconnectFailed,
+}
+
+/// A device identified by its serial number, which is reopened automatically
+/// after being disconnected.
+///
+/// When a transfer fails with [`D3xxError::DeviceNotConnected`],
+/// [`D3xxError::DeviceNotFound`] or [`D3xxError::InvalidHandle`], the device
+/// is closed and a background thread polls [`Device::open_with_serial_number`]
+/// until it succeeds. Transfers made in the meantime fail with
+/// [`D3xxError::DeviceNotConnected`].
+///
+/// Pipe configuration is not restored when the device is reopened; use
+/// [`ReconnectingDevice::connection_events`] to be notified and reapply it
+/// with [`ReconnectingDevice::lock`].
+#[derive(Debug)]
+pub struct ReconnectingDevice {
+    shared: Arc<Shared>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    serial_number: String,
+    policy: ReconnectPolicy,
+    device: Mutex<Option<Device>>,
+    reconnecting: AtomicBool,
+    listeners: Mutex<Vec<mpsc::Sender<ConnectionEvent>>>,
+}
+
+impl ReconnectingDevice {
+    /// Open the device with the given serial number.
+    ///
+    /// # Errors
+    /// Any error from [`Device::open_with_serial_number`].
+    pub fn open(serial_number: &str, policy: ReconnectPolicy) -> Result<ReconnectingDevice> {
+        let device = Device::open_with_serial_number(serial_number)?;
+        Ok(Self {
+            shared: Arc::new(Shared {
+                serial_number: serial_number.to_owned(),
+                policy,
+                device: Mutex::new(Some(device)),
+                reconnecting: AtomicBool::new(false),
+                listeners: Mutex::default(),
+            }),
+        })
+    }
+
+    /// The serial number of the device.
+    pub fn serial_number(&self) -> &str {
+        &self.shared.serial_number
+    }
+
+    /// The policy used to reopen the device.
+    pub fn policy(&self) -> ReconnectPolicy {
+        self.shared.policy
+    }
+
+    /// Whether the device is currently open.
+    pub fn is_connected(&self) -> bool {
+        self.lock().is_some()
+    }
+
+    /// Create a channel receiving every subsequent [`ConnectionEvent`].
+    pub fn connection_events(&self) -> mpsc::Receiver<ConnectionEvent> {
+        let (tx, rx) = mpsc::channel();
+        lock(&self.shared.listeners).push(tx);
+        rx
+    }
+
+    /// Lock the device, which is `None` while disconnected.
+    pub fn lock(&self) -> MutexGuard<'_, Option<Device>> {
+        lock(&self.shared.device)
+    }
+
+    /// Writes data to the specified pipe. See [`Device::write`].
+    pub fn write(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
+        self.with_device(|device| device.write(pipe, buf))
+    }
+
+    /// Reads data from the specified pipe. See [`Device::read`].
+    pub fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        self.with_device(|device| device.read(pipe, buf))
+    }
+
+    /// Run `f` on the device, starting to reconnect if it has been lost.
+    fn with_device<T>(&self, f: impl FnOnce(&Device) -> Result<T>) -> Result<T> {
+        let mut guard = self.lock();
+        let Some(device) = guard.as_ref() else {
+            drop(guard);
+            self.start_reconnect();
+            return Err(D3xxError::DeviceNotConnected);
+        };
+        let result = f(device);
+        if let Err(
+            D3xxError::DeviceNotConnected | D3xxError::DeviceNotFound | D3xxError::InvalidHandle,
+        ) = result
+        {
+            guard.take();
+            drop(guard);
+            self.shared.notify(ConnectionEvent::Disconnected);
+            self.start_reconnect();
+        }
+        result
+    }
+
+    /// Spawn the reconnect thread, unless it is already running.
+    fn start_reconnect(&self) {
+        if self.shared.reconnecting.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        // The thread only holds a weak reference so it stops once the
+        // device is dropped.
+        let shared = Arc::downgrade(&self.shared);
+        std::thread::spawn(move || reconnect(shared));
+    }
+}
+
+impl Shared {
+    fn notify(&self, event: ConnectionEvent) {
+        lock(&self.listeners).retain(|tx| tx.send(event).is_ok());
+    }
+}
+
+/// Try to reopen the device until it succeeds, the policy gives up, or the
+/// device is dropped.
+fn reconnect(shared: Weak<Shared>) {
+    let mut attempt = 0;
+    loop {
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        let policy = shared.policy;
+        if policy.max_attempts.is_some_and(|max| attempt >= max) {
+            shared.reconnecting.store(false, Ordering::Release);
+            shared.notify(ConnectionEvent::ReconnectFailed);
+            return;
+        }
+        attempt += 1;
+        if let Ok(device) = Device::open_with_serial_number(&shared.serial_number) {
+            *lock(&shared.device) = Some(device);
+            shared.reconnecting.store(false, Ordering::Release);
+            shared.notify(ConnectionEvent::Reconnected);
+            return;
+        }
+        drop(shared);
+        std::thread::sleep(policy.reconnect_interval);
+    }
+}
+
+/// Lock a mutex, ignoring poisoning as a panic cannot leave the contents in an
+/// inconsistent state.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
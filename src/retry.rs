@@ -0,0 +1,59 @@
+//! Retrying of transfers which fail with transient errors.
+
+use std::time::Duration;
+
+use crate::{D3xxError, Result};
+
+/// Controls how a failed transfer is retried.
+///
+/// Only transient errors ([`D3xxError::Timeout`] and [`D3xxError::Busy`])
+/// are retried. Any other error is returned immediately.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of attempts, including the first one.
+    pub max_attempts: usize,
+    /// The delay between attempts.
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Create a policy making up to `max_attempts` attempts, waiting `delay`
+    /// between each.
+    pub fn new(max_attempts: usize, delay: Duration) -> RetryPolicy {
+        Self {
+            max_attempts,
+            delay,
+        }
+    }
+
+    /// A policy which makes a single attempt.
+    pub fn none() -> RetryPolicy {
+        Self::new(1, Duration::ZERO)
+    }
+
+    /// Run `f` until it succeeds, fails with a non-transient error, or the
+    /// maximum number of attempts is reached.
+    pub(crate) fn run<T>(&self, mut f: impl FnMut() -> Result<T>) -> Result<T> {
+        let mut attempt = 1;
+        loop {
+            match f() {
+                Err(e) if attempt < self.max_attempts && is_retryable(&e) => {
+                    attempt += 1;
+                    std::thread::sleep(self.delay);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Check if the error is worth retrying.
+fn is_retryable(e: &D3xxError) -> bool {
+    matches!(e, D3xxError::Timeout | D3xxError::Busy)
+}
@@ -0,0 +1,239 @@
+//! Continuous streaming from an IN pipe into a ring buffer, enabled by the
+//! `ring-buffer` feature.
+
+use std::{
+    cell::UnsafeCell,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::{D3xxError, Device, Pipe, Result, SharedHandle};
+
+/// How long the reader thread waits for the consumer when the ring is full.
+const FULL_BACKOFF: Duration = Duration::from_micros(100);
+
+/// How long stopping waits between attempts to abort the read in progress.
+const ABORT_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Streams data from an IN pipe into a pre-allocated ring buffer.
+///
+/// A background thread owns the device and continuously reads from the pipe
+/// into the free part of the ring, while the consumer looks at the data in place
+/// using [`RingPipeReader::read_slice`] and releases it using
+/// [`RingPipeReader::consume`]. The head and tail of the ring are atomics, so
+/// neither side takes a lock on the hot path.
+///
+/// # Choosing a capacity
+/// Each read is at most a quarter of the capacity, so the reader thread can
+/// keep a transfer in flight while the consumer processes earlier data.
+/// A larger ring allows larger transfers and absorbs longer consumer stalls,
+/// giving better throughput, but a transfer only completes once it is full or
+/// the pipe times out, so data takes longer to become visible. A smaller ring
+/// makes data available sooner at the cost of more transfers per byte, and
+/// fills up quicker if the consumer falls behind.
+///
+/// ```no_run
+/// use ft60x_rs::{Device, Pipe, RingPipeReader};
+///
+/// let device = Device::open_by_index(0)?;
+/// let mut reader = RingPipeReader::new(device, Pipe::In0, 1 << 24)?;
+/// loop {
+///     let n = reader.read_slice().len();
+///     // Process the data...
+///     reader.consume(n);
+/// #   break;
+/// }
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug)]
+pub struct RingPipeReader {
+    ring: Arc<Ring>,
+    pipe: Pipe,
+    /// Used to abort the read in progress when stopping.
+    handle: SharedHandle,
+    thread: Option<JoinHandle<Device>>,
+}
+
+/// State shared between the consumer and the reader thread.
+#[derive(Debug)]
+struct Ring {
+    buf: Box<[UnsafeCell<u8>]>,
+    /// Total number of bytes written by the reader thread.
+    head: AtomicUsize,
+    /// Total number of bytes consumed.
+    tail: AtomicUsize,
+    stop: AtomicBool,
+    error: Mutex<Option<D3xxError>>,
+}
+
+// The reader thread only writes to the free part of the ring and the consumer
+// only reads from the filled part, which are kept disjoint by `head` and `tail`.
+unsafe impl Sync for Ring {}
+
+impl RingPipeReader {
+    /// Start streaming from `pipe` into a ring of `capacity` bytes.
+    ///
+    /// # Errors
+    /// Returns [`D3xxError::InvalidParameter`] if `pipe` is not an IN pipe or
+    /// `capacity` is not a non-zero power of two.
+    pub fn new(device: Device, pipe: Pipe, capacity: usize) -> Result<RingPipeReader> {
+        if !pipe.is_read_pipe() || !capacity.is_power_of_two() {
+            Err(D3xxError::InvalidParameter)?;
+        }
+        let ring = Arc::new(Ring {
+            buf: (0..capacity).map(|_| UnsafeCell::new(0)).collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            stop: AtomicBool::new(false),
+            error: Mutex::new(None),
+        });
+        let handle = device.shared_handle();
+        let thread = {
+            let ring = Arc::clone(&ring);
+            std::thread::spawn(move || {
+                ring.fill(&device, pipe);
+                device
+            })
+        };
+        Ok(Self {
+            ring,
+            pipe,
+            handle,
+            thread: Some(thread),
+        })
+    }
+
+    /// The pipe being read from.
+    pub fn pipe(&self) -> Pipe {
+        self.pipe
+    }
+
+    /// The size of the ring in bytes.
+    pub fn capacity(&self) -> usize {
+        self.ring.buf.len()
+    }
+
+    /// The number of bytes read but not yet consumed.
+    pub fn len(&self) -> usize {
+        self.ring.filled()
+    }
+
+    /// Whether there is no data waiting to be consumed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The data read but not yet consumed, up to the end of the ring.
+    ///
+    /// When the data wraps around the end of the ring, only the first part is
+    /// returned; the rest is returned once it has been consumed.
+    pub fn read_slice(&self) -> &[u8] {
+        let tail = self.ring.tail.load(Ordering::Relaxed);
+        let start = tail & (self.capacity() - 1);
+        let len = self.ring.filled().min(self.capacity() - start);
+        // SAFETY: this region is filled and is not written to by the reader
+        // thread until it is consumed, which requires `&mut self`.
+        unsafe { std::slice::from_raw_parts(self.ring.ptr(start), len) }
+    }
+
+    /// Release the first `n` bytes of data, allowing the space to be reused.
+    ///
+    /// # Panics
+    /// Panics if `n` is greater than [`RingPipeReader::len`].
+    pub fn consume(&mut self, n: usize) {
+        assert!(n <= self.len(), "consumed more data than available");
+        self.ring.tail.fetch_add(n, Ordering::Release);
+    }
+
+    /// Take the error which stopped the reader thread, if any.
+    ///
+    /// Timeouts are not errors; the reader thread simply tries again.
+    pub fn take_error(&self) -> Option<D3xxError> {
+        self.ring
+            .error
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+
+    /// Stop the reader thread and return the device.
+    ///
+    /// The read in progress is aborted, and any data not yet consumed is
+    /// discarded.
+    pub fn into_device(mut self) -> Device {
+        self.stop().expect("reader thread is only stopped once")
+    }
+
+    /// Stop the reader thread, aborting the transfer in progress.
+    fn stop(&mut self) -> Option<Device> {
+        self.ring.stop.store(true, Ordering::Relaxed);
+        let thread = self.thread.take()?;
+        // The device is owned by the thread, and is kept open by its result
+        // after it exits. The thread may start another read just after an
+        // abort, so keep aborting until it notices the stop flag.
+        while !thread.is_finished() {
+            let _ = self.handle.abort(self.pipe);
+            std::thread::sleep(ABORT_INTERVAL);
+        }
+        match thread.join() {
+            Ok(device) => Some(device),
+            Err(e) => std::panic::resume_unwind(e),
+        }
+    }
+}
+
+impl Drop for RingPipeReader {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl Ring {
+    /// Pointer to the byte at `offset` in the ring.
+    fn ptr(&self, offset: usize) -> *mut u8 {
+        // SAFETY: `offset` is always less than the capacity.
+        unsafe { UnsafeCell::raw_get(self.buf.as_ptr()).add(offset) }
+    }
+
+    /// The number of filled bytes.
+    fn filled(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        head - self.tail.load(Ordering::Acquire)
+    }
+
+    /// Read from the pipe into the free part of the ring until stopped or an
+    /// error occurs.
+    fn fill(&self, device: &Device, pipe: Pipe) {
+        let capacity = self.buf.len();
+        let max_transfer = (capacity / 4).max(1);
+        while !self.stop.load(Ordering::Relaxed) {
+            let head = self.head.load(Ordering::Relaxed);
+            let start = head & (capacity - 1);
+            let free = capacity - self.filled();
+            let len = free.min(capacity - start).min(max_transfer);
+            if len == 0 {
+                std::thread::sleep(FULL_BACKOFF);
+                continue;
+            }
+            // SAFETY: this region is free, so the consumer does not look at it
+            // until `head` is advanced past it.
+            let buf = unsafe { std::slice::from_raw_parts_mut(self.ptr(start), len) };
+            match device.read(pipe, buf) {
+                Ok(n) => {
+                    self.head.fetch_add(n, Ordering::Release);
+                }
+                Err(D3xxError::Timeout) => (),
+                // The read was aborted by `RingPipeReader::stop`.
+                Err(_) if self.stop.load(Ordering::Relaxed) => return,
+                Err(e) => {
+                    *self.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(e);
+                    return;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Serde support for types wrapping raw D3XX structures.
+//!
+//! Types which are plain Rust data derive their implementations directly,
+//! while the types here are converted to and from an intermediate
+//! representation with human-readable field names.
+
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ffi::types::{self, FT_DEVICE_LIST_INFO_NODE};
+use crate::{DeviceInfo, Pipe, PipeInfo, PipeType};
+
+/// Serialized form of [`DeviceInfo`]. The raw handle is intentionally
+/// left out, as it is meaningless once deserialized.
+#[derive(Serialize, Deserialize)]
+struct DeviceInfoRepr {
+    index: usize,
+    flags: u32,
+    #[serde(rename = "type")]
+    type_: u32,
+    vendor_id: u16,
+    product_id: u16,
+    location_id: u32,
+    serial_number: String,
+    description: String,
+}
+
+impl Serialize for DeviceInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        DeviceInfoRepr {
+            index: self.index(),
+            flags: self.flags(),
+            type_: self.type_(),
+            vendor_id: self.vendor_id(),
+            product_id: self.product_id(),
+            location_id: self.location_identifier(),
+            serial_number: self.serial_number().map_err(ser::Error::custom)?,
+            description: self.description().map_err(ser::Error::custom)?,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DeviceInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = DeviceInfoRepr::deserialize(deserializer)?;
+        let mut node = FT_DEVICE_LIST_INFO_NODE {
+            Flags: repr.flags as _,
+            Type: repr.type_ as _,
+            ID: ((repr.vendor_id as u32) << 16 | repr.product_id as u32) as _,
+            LocId: repr.location_id as _,
+            ..Default::default()
+        };
+        copy_c_str(&mut node.SerialNumber, &repr.serial_number)?;
+        copy_c_str(&mut node.Description, &repr.description)?;
+        Ok(DeviceInfo::new(repr.index, node))
+    }
+}
+
+/// Copy a string into a fixed-size, nul-terminated buffer.
+fn copy_c_str<E: de::Error>(dest: &mut [u8], s: &str) -> Result<(), E> {
+    if s.len() >= dest.len() || s.contains('\0') {
+        return Err(E::invalid_value(
+            de::Unexpected::Str(s),
+            &"a string fitting in the D3XX device info structure",
+        ));
+    }
+    dest[..s.len()].copy_from_slice(s.as_bytes());
+    Ok(())
+}
+
+/// Serialized form of [`PipeInfo`].
+#[derive(Serialize, Deserialize)]
+struct PipeInfoRepr {
+    pipe_type: PipeType,
+    pipe: Pipe,
+    maximum_packet_size: u16,
+    interval: u8,
+}
+
+impl Serialize for PipeInfo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PipeInfoRepr {
+            pipe_type: self.type_().map_err(ser::Error::custom)?,
+            pipe: self.pipe().map_err(ser::Error::custom)?,
+            maximum_packet_size: self.maximum_packet_size() as u16,
+            interval: self.interval(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PipeInfo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = PipeInfoRepr::deserialize(deserializer)?;
+        Ok(PipeInfo {
+            inner: types::FT_PIPE_INFORMATION {
+                PipeType: repr.pipe_type as _,
+                PipeID: repr.pipe as _,
+                MaximumPacketSize: repr.maximum_packet_size,
+                Interval: repr.interval,
+            },
+            companion: None,
+        })
+    }
+}
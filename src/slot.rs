@@ -0,0 +1,77 @@
+//! A placeholder for a device which may not be open.
+
+use crate::Device;
+
+/// A slot which either holds an open device or is empty.
+///
+/// Useful for fixed collections of devices where some entries are not open,
+/// such as one slot per physical port:
+///
+/// ```no_run
+/// use ft60x_rs::{Device, DeviceSlot};
+///
+/// let mut slots: Vec<DeviceSlot> = (0..4).map(|_| DeviceSlot::Empty).collect();
+/// slots[0].insert(Device::open_by_index(0)?);
+/// for device in slots.iter().filter_map(DeviceSlot::device) {
+///     println!("{device}");
+/// }
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub enum DeviceSlot {
+    /// No device.
+    #[default]
+    Empty,
+    /// An open device.
+    Occupied(Device),
+}
+
+impl DeviceSlot {
+    /// Check if the slot holds no device.
+    pub fn is_empty(&self) -> bool {
+        matches!(self, DeviceSlot::Empty)
+    }
+
+    /// Check if the slot holds a device.
+    pub fn is_occupied(&self) -> bool {
+        !self.is_empty()
+    }
+
+    /// The device in the slot, if any.
+    pub fn device(&self) -> Option<&Device> {
+        match self {
+            DeviceSlot::Empty => None,
+            DeviceSlot::Occupied(device) => Some(device),
+        }
+    }
+
+    /// Put a device in the slot, returning the device it previously held.
+    pub fn insert(&mut self, device: Device) -> Option<Device> {
+        std::mem::replace(self, DeviceSlot::Occupied(device)).into_device()
+    }
+
+    /// Take the device out of the slot, leaving it empty.
+    pub fn take(&mut self) -> Option<Device> {
+        std::mem::take(self).into_device()
+    }
+
+    /// Convert the slot into the device it holds, if any.
+    pub fn into_device(self) -> Option<Device> {
+        match self {
+            DeviceSlot::Empty => None,
+            DeviceSlot::Occupied(device) => Some(device),
+        }
+    }
+}
+
+impl From<Device> for DeviceSlot {
+    fn from(device: Device) -> Self {
+        DeviceSlot::Occupied(device)
+    }
+}
+
+impl From<Option<Device>> for DeviceSlot {
+    fn from(device: Option<Device>) -> Self {
+        device.map_or(DeviceSlot::Empty, DeviceSlot::Occupied)
+    }
+}
@@ -0,0 +1,200 @@
+//! Capturing the state of a device for later comparison.
+
+use std::fmt::{Debug, Display};
+
+use crate::{d3xx_version, Device, DeviceDescriptor, Pipe, PipeConfig, PipeInfo, Result, Version};
+
+/// The state of a device at a point in time, obtained through [`Device::snapshot`].
+///
+/// Useful for comparing the state before and after a configuration change:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use ft60x_rs::{Device, Pipe};
+///
+/// let device = Device::open_by_index(0)?;
+/// let before = device.snapshot()?;
+/// device.set_timeout(Pipe::In0, Duration::from_millis(100))?;
+/// for change in before.diff(&device.snapshot()?) {
+///     println!("{change}");
+/// }
+/// # Ok::<(), ft60x_rs::D3xxError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeviceSnapshot {
+    /// The USB device descriptor.
+    pub device_descriptor: DeviceDescriptor,
+    /// The D3XX driver version.
+    pub driver_version: Version,
+    /// The D3XX library version.
+    pub library_version: Version,
+    /// The configuration of each pipe exposed by the device, IN pipes first.
+    ///
+    /// As with [`Device::snapshot_pipe_config`], timeouts and stream sizes are
+    /// the ones last set through the handle the snapshot was taken with, and
+    /// the latency timer is only available on Windows.
+    pub pipe_configs: Vec<(Pipe, PipeConfig)>,
+    /// Information about each pipe exposed by the device, IN pipes first.
+    pub pipe_infos: Vec<(Pipe, PipeInfo)>,
+    /// The vendor ID and product ID of the device.
+    pub vid_pid: (u16, u16),
+}
+
+impl DeviceSnapshot {
+    /// Capture the current state of the device.
+    pub(crate) fn capture(device: &Device) -> Result<Self> {
+        let pipe_infos = device
+            .pipe_info_all()?
+            .into_iter()
+            .map(|info| Ok((info.pipe()?, info)))
+            .collect::<Result<Vec<_>>>()?;
+        let pipe_configs = pipe_infos
+            .iter()
+            .map(|&(pipe, _)| Ok((pipe, device.snapshot_pipe_config(pipe)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let (vid, pid) = device.vid_pid()?;
+        Ok(Self {
+            device_descriptor: device.device_descriptor()?,
+            driver_version: device.driver_version()?,
+            library_version: d3xx_version(),
+            pipe_configs,
+            pipe_infos,
+            vid_pid: (vid as u16, pid as u16),
+        })
+    }
+
+    /// List the fields which differ between this snapshot and `other`.
+    ///
+    /// Each entry names the field and gives the value in this snapshot
+    /// followed by the value in `other`, e.g. `driver_version: 1.0.0.2 -> 1.0.0.5`.
+    /// Pipes present in only one of the snapshots are reported as such.
+    pub fn diff(&self, other: &DeviceSnapshot) -> Vec<String> {
+        let mut changes = Vec::new();
+        let old = &self.device_descriptor;
+        let new = &other.device_descriptor;
+        for (name, old, new) in [
+            (
+                "usb_specification_number",
+                old.usb_specification_number(),
+                new.usb_specification_number(),
+            ),
+            ("class_code", old.class_code(), new.class_code()),
+            ("subclass_code", old.subclass_code(), new.subclass_code()),
+            ("protocol_code", old.protocol_code(), new.protocol_code()),
+            (
+                "max_packet_size",
+                old.max_packet_size(),
+                new.max_packet_size(),
+            ),
+            ("vendor_id", old.vendor_id(), new.vendor_id()),
+            ("product_id", old.product_id(), new.product_id()),
+            ("release_number", old.release_number(), new.release_number()),
+            (
+                "num_configurations",
+                old.num_configurations(),
+                new.num_configurations(),
+            ),
+        ] {
+            push_change(
+                &mut changes,
+                &format!("device_descriptor.{name}"),
+                &old,
+                &new,
+            );
+        }
+        push_change(
+            &mut changes,
+            "driver_version",
+            &self.driver_version,
+            &other.driver_version,
+        );
+        push_change(
+            &mut changes,
+            "library_version",
+            &self.library_version,
+            &other.library_version,
+        );
+        diff_pipes(
+            &mut changes,
+            "pipe_configs",
+            &self.pipe_configs,
+            &other.pipe_configs,
+        );
+        diff_pipes(
+            &mut changes,
+            "pipe_infos",
+            &self.pipe_infos,
+            &other.pipe_infos,
+        );
+        if self.vid_pid != other.vid_pid {
+            changes.push(format!(
+                "vid_pid: {:04X}:{:04X} -> {:04X}:{:04X}",
+                self.vid_pid.0, self.vid_pid.1, other.vid_pid.0, other.vid_pid.1
+            ));
+        }
+        changes
+    }
+}
+
+/// Record a change to `field` if `old` and `new` differ.
+fn push_change<T: PartialEq + Display>(changes: &mut Vec<String>, field: &str, old: &T, new: &T) {
+    if old != new {
+        changes.push(format!("{field}: {old} -> {new}"));
+    }
+}
+
+/// Record the pipes which were added, removed or changed between `old` and `new`.
+fn diff_pipes<T: PartialEq + Debug>(
+    changes: &mut Vec<String>,
+    field: &str,
+    old: &[(Pipe, T)],
+    new: &[(Pipe, T)],
+) {
+    for pipe in Pipe::all() {
+        let find = |entries: &'_ [(Pipe, T)]| {
+            entries
+                .iter()
+                .find(|(p, _)| *p == pipe)
+                .map(|(_, value)| format!("{value:?}"))
+        };
+        match (find(old), find(new)) {
+            (Some(old), Some(new)) if old != new => {
+                changes.push(format!("{field}[{pipe:?}]: {old} -> {new}"))
+            }
+            (Some(_), None) => changes.push(format!("{field}[{pipe:?}]: removed")),
+            (None, Some(_)) => changes.push(format!("{field}[{pipe:?}]: added")),
+            _ => {}
+        }
+    }
+}
+
+/// Shows one field per line, with one line per pipe.
+impl Display for DeviceSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Device {:04X}:{:04X}", self.vid_pid.0, self.vid_pid.1)?;
+        writeln!(f, "  Descriptor: {}", self.device_descriptor)?;
+        writeln!(f, "  Driver version: {}", self.driver_version)?;
+        writeln!(f, "  Library version: {}", self.library_version)?;
+        writeln!(f, "  Pipes:")?;
+        for (pipe, info) in &self.pipe_infos {
+            write!(
+                f,
+                "    {pipe:?}: max packet size {}, interval {}",
+                info.maximum_packet_size(),
+                info.interval()
+            )?;
+            if let Some((_, config)) = self.pipe_configs.iter().find(|(p, _)| p == pipe) {
+                write!(f, ", timeout {:?}", config.timeout)?;
+                match config.stream_size {
+                    Some(size) => write!(f, ", stream size {size}")?,
+                    None => write!(f, ", no stream size")?,
+                }
+                if let Some(latency_ms) = config.latency_ms {
+                    write!(f, ", latency timer {latency_ms} ms")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! Transfer statistics, enabled by the `stats` feature.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{D3xxError, Result};
+
+/// Counters tracking the transfers performed on a [`Device`](crate::Device).
+///
+/// Obtained through [`Device::stats`](crate::Device::stats).
+#[derive(Debug, Default)]
+pub struct TransferStats {
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    read_errors: AtomicU64,
+    write_errors: AtomicU64,
+    timeouts: AtomicU64,
+    retries: AtomicU64,
+}
+
+impl TransferStats {
+    /// Total number of bytes read.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// Total number of bytes written.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written.load(Ordering::Relaxed)
+    }
+
+    /// Number of reads which failed, including timeouts.
+    pub fn read_errors(&self) -> u64 {
+        self.read_errors.load(Ordering::Relaxed)
+    }
+
+    /// Number of writes which failed, including timeouts.
+    pub fn write_errors(&self) -> u64 {
+        self.write_errors.load(Ordering::Relaxed)
+    }
+
+    /// Number of reads or writes which timed out.
+    pub fn timeouts(&self) -> u64 {
+        self.timeouts.load(Ordering::Relaxed)
+    }
+
+    /// Number of transfers retried according to a [`RetryPolicy`](crate::RetryPolicy).
+    pub fn retries(&self) -> u64 {
+        self.retries.load(Ordering::Relaxed)
+    }
+
+    /// Reset all counters to zero.
+    pub(crate) fn reset(&self) {
+        for counter in [
+            &self.bytes_read,
+            &self.bytes_written,
+            &self.read_errors,
+            &self.write_errors,
+            &self.timeouts,
+            &self.retries,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the result of a read.
+    pub(crate) fn record_read(&self, result: &Result<usize>) {
+        Self::record(result, &self.bytes_read, &self.read_errors, &self.timeouts);
+    }
+
+    /// Record the result of a write.
+    pub(crate) fn record_write(&self, result: &Result<usize>) {
+        Self::record(
+            result,
+            &self.bytes_written,
+            &self.write_errors,
+            &self.timeouts,
+        );
+    }
+
+    /// Record a number of retried transfers.
+    pub(crate) fn record_retries(&self, retries: u64) {
+        self.retries.fetch_add(retries, Ordering::Relaxed);
+    }
+
+    fn record(result: &Result<usize>, bytes: &AtomicU64, errors: &AtomicU64, timeouts: &AtomicU64) {
+        match result {
+            Ok(n) => {
+                bytes.fetch_add(*n as u64, Ordering::Relaxed);
+            }
+            Err(e) => {
+                errors.fetch_add(1, Ordering::Relaxed);
+                if matches!(e, D3xxError::Timeout) {
+                    timeouts.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+}
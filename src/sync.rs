@@ -0,0 +1,51 @@
+//! Shared access to a [`Device`] from multiple threads.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::{Device, Pipe, Result};
+
+/// A [`Device`] which can be cloned and shared between threads.
+///
+/// Each clone refers to the same underlying device, and every operation
+/// locks the device for its full duration. This means a blocking read on
+/// one thread will hold up a write on another thread until the read completes
+/// or times out, so short pipe timeouts are recommended.
+#[derive(Debug, Clone)]
+pub struct ThreadSafeDevice {
+    inner: Arc<Mutex<Device>>,
+}
+
+impl ThreadSafeDevice {
+    /// Wrap the given device for shared access.
+    pub fn new(device: Device) -> ThreadSafeDevice {
+        Self {
+            inner: Arc::new(Mutex::new(device)),
+        }
+    }
+
+    /// Lock the device for exclusive access.
+    ///
+    /// Useful for performing several operations without another thread
+    /// interleaving its own.
+    pub fn lock(&self) -> MutexGuard<'_, Device> {
+        // A panic while holding the lock cannot leave the device in an
+        // inconsistent state, so poisoning is ignored.
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Writes data to the specified pipe. See [`Device::write`].
+    pub fn write(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
+        self.lock().write(pipe, buf)
+    }
+
+    /// Reads data from the specified pipe. See [`Device::read`].
+    pub fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        self.lock().read(pipe, buf)
+    }
+}
+
+impl From<Device> for ThreadSafeDevice {
+    fn from(device: Device) -> Self {
+        Self::new(device)
+    }
+}
@@ -0,0 +1,220 @@
+//! Transfer logic built on single pipe reads and writes, kept apart from
+//! [`Device`] so it can be tested without hardware.
+
+use crate::{D3xxError, Device, Pipe, Result, RetryPolicy};
+
+/// Single reads and writes on a pipe.
+pub(crate) trait PipeTransfer {
+    /// Write data to the pipe, returning the number of bytes written.
+    fn write(&self, pipe: Pipe, buf: &[u8]) -> Result<usize>;
+
+    /// Read data from the pipe, returning the number of bytes read.
+    fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize>;
+}
+
+impl PipeTransfer for Device {
+    fn write(&self, pipe: Pipe, buf: &[u8]) -> Result<usize> {
+        Device::write(self, pipe, buf)
+    }
+
+    fn read(&self, pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+        Device::read(self, pipe, buf)
+    }
+}
+
+/// Write the entire buffer, issuing as many writes as needed. See [`Device::write_all`].
+pub(crate) fn write_all(io: &impl PipeTransfer, pipe: Pipe, buf: &[u8]) -> Result<()> {
+    let mut offset = 0;
+    while offset < buf.len() {
+        match io.write(pipe, &buf[offset..])? {
+            0 => Err(D3xxError::Timeout)?,
+            n => offset += n,
+        }
+    }
+    Ok(())
+}
+
+/// Fill the entire buffer, issuing as many reads as needed. See [`Device::read_exact`].
+pub(crate) fn read_exact(io: &impl PipeTransfer, pipe: Pipe, buf: &mut [u8]) -> Result<()> {
+    let mut offset = 0;
+    while offset < buf.len() {
+        match io.read(pipe, &mut buf[offset..])? {
+            0 => Err(D3xxError::Timeout)?,
+            n => offset += n,
+        }
+    }
+    Ok(())
+}
+
+/// Write data, retrying according to the policy. Returns the result and the
+/// number of retries made.
+pub(crate) fn write_with_retry(
+    io: &impl PipeTransfer,
+    pipe: Pipe,
+    buf: &[u8],
+    policy: RetryPolicy,
+) -> (Result<usize>, u64) {
+    let mut attempts = 0;
+    let result = policy.run(|| {
+        attempts += 1;
+        io.write(pipe, buf)
+    });
+    (result, attempts - 1)
+}
+
+/// Read data, retrying according to the policy. Returns the result and the
+/// number of retries made.
+pub(crate) fn read_with_retry(
+    io: &impl PipeTransfer,
+    pipe: Pipe,
+    buf: &mut [u8],
+    policy: RetryPolicy,
+) -> (Result<usize>, u64) {
+    let mut attempts = 0;
+    let result = policy.run(|| {
+        attempts += 1;
+        io.read(pipe, buf)
+    });
+    (result, attempts - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::VecDeque, time::Duration};
+
+    use super::*;
+
+    /// Simulates a pipe with queued read data and a limit on how much each
+    /// write accepts.
+    #[derive(Default)]
+    struct MockPipe {
+        /// The outcome of each read. Data which does not fit in the buffer is
+        /// kept for the next read. Reads time out once the queue is empty.
+        reads: RefCell<VecDeque<Result<Vec<u8>>>>,
+        /// The outcome of each write, as the most bytes accepted. Writes
+        /// accept everything once the queue is empty.
+        writes: RefCell<VecDeque<Result<usize>>>,
+        /// Everything written so far.
+        written: RefCell<Vec<u8>>,
+    }
+
+    impl MockPipe {
+        fn with_reads(reads: impl IntoIterator<Item = Result<Vec<u8>>>) -> MockPipe {
+            MockPipe {
+                reads: RefCell::new(reads.into_iter().collect()),
+                ..Default::default()
+            }
+        }
+
+        fn with_writes(writes: impl IntoIterator<Item = Result<usize>>) -> MockPipe {
+            MockPipe {
+                writes: RefCell::new(writes.into_iter().collect()),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl PipeTransfer for MockPipe {
+        fn write(&self, _pipe: Pipe, buf: &[u8]) -> Result<usize> {
+            let limit = self
+                .writes
+                .borrow_mut()
+                .pop_front()
+                .unwrap_or(Ok(buf.len()))?;
+            let n = limit.min(buf.len());
+            self.written.borrow_mut().extend_from_slice(&buf[..n]);
+            Ok(n)
+        }
+
+        fn read(&self, _pipe: Pipe, buf: &mut [u8]) -> Result<usize> {
+            let mut reads = self.reads.borrow_mut();
+            let mut data = reads.pop_front().unwrap_or(Err(D3xxError::Timeout))?;
+            let n = data.len().min(buf.len());
+            buf[..n].copy_from_slice(&data[..n]);
+            if n < data.len() {
+                reads.push_front(Ok(data.split_off(n)));
+            }
+            Ok(n)
+        }
+    }
+
+    fn policy(max_attempts: usize) -> RetryPolicy {
+        RetryPolicy::new(max_attempts, Duration::ZERO)
+    }
+
+    #[test]
+    fn read_exact_combines_short_reads() {
+        let pipe = MockPipe::with_reads([Ok(vec![1, 2]), Ok(vec![3, 4, 5, 6])]);
+        let mut buf = [0; 5];
+        read_exact(&pipe, Pipe::In0, &mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3, 4, 5]);
+        let mut rest = [0; 1];
+        read_exact(&pipe, Pipe::In0, &mut rest).unwrap();
+        assert_eq!(rest, [6]);
+    }
+
+    #[test]
+    fn read_exact_fails_on_empty_read() {
+        let pipe = MockPipe::with_reads([Ok(vec![1]), Ok(vec![])]);
+        let mut buf = [0; 4];
+        assert!(matches!(
+            read_exact(&pipe, Pipe::In0, &mut buf),
+            Err(D3xxError::Timeout)
+        ));
+    }
+
+    #[test]
+    fn read_exact_returns_errors() {
+        let pipe = MockPipe::with_reads([Ok(vec![1]), Err(D3xxError::IoError)]);
+        let mut buf = [0; 4];
+        assert!(matches!(
+            read_exact(&pipe, Pipe::In0, &mut buf),
+            Err(D3xxError::IoError)
+        ));
+    }
+
+    #[test]
+    fn write_all_combines_short_writes() {
+        let pipe = MockPipe::with_writes([Ok(2), Ok(1)]);
+        write_all(&pipe, Pipe::Out0, &[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(*pipe.written.borrow(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn write_all_fails_on_empty_write() {
+        let pipe = MockPipe::with_writes([Ok(2), Ok(0)]);
+        assert!(matches!(
+            write_all(&pipe, Pipe::Out0, &[1, 2, 3]),
+            Err(D3xxError::Timeout)
+        ));
+        assert_eq!(*pipe.written.borrow(), [1, 2]);
+    }
+
+    #[test]
+    fn retry_recovers_from_transient_errors() {
+        let pipe =
+            MockPipe::with_reads([Err(D3xxError::Busy), Err(D3xxError::Timeout), Ok(vec![7])]);
+        let mut buf = [0; 1];
+        let (result, retries) = read_with_retry(&pipe, Pipe::In0, &mut buf, policy(3));
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(retries, 2);
+        assert_eq!(buf, [7]);
+    }
+
+    #[test]
+    fn retry_gives_up_after_max_attempts() {
+        let pipe = MockPipe::with_writes([Err(D3xxError::Busy), Err(D3xxError::Busy), Ok(1)]);
+        let (result, retries) = write_with_retry(&pipe, Pipe::Out0, &[1], policy(2));
+        assert!(matches!(result, Err(D3xxError::Busy)));
+        assert_eq!(retries, 1);
+        assert!(pipe.written.borrow().is_empty());
+    }
+
+    #[test]
+    fn retry_does_not_retry_fatal_errors() {
+        let pipe = MockPipe::with_writes([Err(D3xxError::DeviceNotConnected), Ok(1)]);
+        let (result, retries) = write_with_retry(&pipe, Pipe::Out0, &[1], policy(5));
+        assert!(matches!(result, Err(D3xxError::DeviceNotConnected)));
+        assert_eq!(retries, 0);
+    }
+}